@@ -2,6 +2,16 @@ use schemars::JsonSchema;
 use serde::Deserialize;
 use serde::Serialize;
 
+#[cfg(feature = "regex")]
+use ara_source::SourceMap;
+#[cfg(feature = "regex")]
+use regex::Regex;
+
+#[cfg(feature = "regex")]
+use crate::error::Error;
+use crate::range_contains;
+use crate::ranges_overlap;
+
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum AnnotationType {
@@ -13,10 +23,16 @@ pub enum AnnotationType {
 #[serde(rename_all = "snake_case")]
 pub struct Annotation {
     pub message: Option<String>,
+    #[serde(default)]
+    pub note: Option<String>,
+    #[serde(default)]
+    pub group: Option<usize>,
     pub r#type: AnnotationType,
     pub origin: String,
     pub from: usize,
     pub to: usize,
+    #[serde(default)]
+    pub ranges: Vec<(usize, usize)>,
 }
 
 impl Annotation {
@@ -40,9 +56,12 @@ impl Annotation {
         Self {
             r#type,
             message: None,
+            note: None,
+            group: None,
             origin: origin.into(),
             from,
             to,
+            ranges: vec![],
         }
     }
 
@@ -78,6 +97,143 @@ impl Annotation {
         Self::new(AnnotationType::Secondary, origin, from, to)
     }
 
+    /// Create a secondary annotation without an origin.
+    ///
+    /// The origin is resolved to the default origin of the report when rendering,
+    /// see `Report::with_default_origin`.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// use ara_reporting::annotation::Annotation;
+    /// use ara_reporting::annotation::AnnotationType;
+    ///
+    /// let annotation = Annotation::here(0, 5);
+    ///
+    /// assert_eq!(annotation.r#type, AnnotationType::Secondary);
+    /// assert!(annotation.origin.is_empty());
+    /// ```
+    pub fn here(from: usize, to: usize) -> Self {
+        Self::new(AnnotationType::Secondary, String::new(), from, to)
+    }
+
+    /// Create a secondary annotation at the `nth` match, starting from 0, of the given pattern
+    /// in the content of the given origin.
+    ///
+    /// Returns `Error::FileMissing` if the origin is not in the source map, and
+    /// `Error::MatchTooLarge` if the pattern has fewer matches.
+    ///
+    /// Requires the `regex` feature.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// use ara_source::source::Source;
+    /// use ara_source::source::SourceKind;
+    /// use ara_source::SourceMap;
+    /// use regex::Regex;
+    ///
+    /// use ara_reporting::annotation::Annotation;
+    /// use ara_reporting::error::Error;
+    ///
+    /// let source = SourceMap::new(vec![
+    ///     Source::new(SourceKind::Script, "main.ara", "$a = 1;\n$b = $a;"),
+    /// ]);
+    ///
+    /// let pattern = Regex::new(r"\$a\b").unwrap();
+    ///
+    /// let annotation = Annotation::from_regex(&source, "main.ara", &pattern, 1).unwrap();
+    /// assert_eq!(annotation, Annotation::secondary("main.ara", 13, 15));
+    ///
+    /// assert!(matches!(
+    ///     Annotation::from_regex(&source, "main.ara", &pattern, 2),
+    ///     Err(Error::MatchTooLarge { given: 2, max: 2 })
+    /// ));
+    /// assert!(matches!(
+    ///     Annotation::from_regex(&source, "lib.ara", &pattern, 0),
+    ///     Err(Error::FileMissing)
+    /// ));
+    /// ```
+    #[cfg(feature = "regex")]
+    pub fn from_regex<O: Into<String>>(
+        source_map: &SourceMap,
+        origin: O,
+        pattern: &Regex,
+        nth: usize,
+    ) -> Result<Self, Error> {
+        let origin = origin.into();
+        let content = &source_map
+            .sources
+            .iter()
+            .find(|source| source.name() == origin)
+            .ok_or(Error::FileMissing)?
+            .content;
+
+        match pattern.find_iter(content).nth(nth) {
+            Some(found) => Ok(Self::secondary(origin, found.start(), found.end())),
+            None => Err(Error::MatchTooLarge {
+                given: nth,
+                max: pattern.find_iter(content).count(),
+            }),
+        }
+    }
+
+    /// Create a secondary annotation covering several ranges of the same origin.
+    ///
+    /// Unlike adding an annotation per range, every range is underlined with the message
+    /// and style of this one annotation, and the ranges are deduplicated together, see
+    /// `Issue::dedup_annotations`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ranges` is empty.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// use ara_source::source::Source;
+    /// use ara_source::source::SourceKind;
+    /// use ara_source::SourceMap;
+    ///
+    /// use ara_reporting::annotation::Annotation;
+    /// use ara_reporting::builder::ColorChoice;
+    /// use ara_reporting::builder::ReportBuilder;
+    /// use ara_reporting::issue::Issue;
+    /// use ara_reporting::Report;
+    ///
+    /// let annotation = Annotation::multi("main.ara", vec![(5, 7), (10, 12)])
+    ///     .with_message("shadowed `$a`");
+    ///
+    /// assert_eq!((annotation.from, annotation.to), (5, 7));
+    /// assert_eq!(annotation.ranges, vec![(10, 12)]);
+    ///
+    /// let json = serde_json::to_string(&annotation).unwrap();
+    /// assert_eq!(serde_json::from_str::<Annotation>(&json).unwrap(), annotation);
+    ///
+    /// let source = SourceMap::new(vec![
+    ///     Source::new(SourceKind::Script, "main.ara", "echo $a . $a;"),
+    /// ]);
+    ///
+    /// let report = Report::new().with_issue(Issue::warning("W0001", "...").with_annotation(annotation));
+    ///
+    /// let output = ReportBuilder::new(&source)
+    ///     .with_colors(ColorChoice::Never)
+    ///     .as_string(&report)
+    ///     .unwrap();
+    ///
+    /// assert_eq!(output.matches("shadowed `$a`").count(), 2);
+    /// ```
+    pub fn multi<O: Into<String>>(origin: O, ranges: Vec<(usize, usize)>) -> Self {
+        let (from, to) = *ranges
+            .first()
+            .expect("a multi-range annotation needs at least one range");
+
+        Self {
+            ranges: ranges.into_iter().skip(1).collect(),
+            ..Self::new(AnnotationType::Secondary, origin, from, to)
+        }
+    }
+
     /// Set the message of this annotation.
     ///
     /// Example:
@@ -96,4 +252,179 @@ impl Annotation {
 
         self
     }
+
+    /// Set the note of this annotation.
+    ///
+    /// A note is a longer explanation of the annotation message, rendered as a dimmed
+    /// continuation line beneath the message.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// use ara_reporting::annotation::Annotation;
+    ///
+    /// let annotation = Annotation::secondary("main.ara", 10, 12)
+    ///     .with_message("this is `int`")
+    ///     .with_note("because `1` is an integer literal");
+    ///
+    /// assert_eq!(annotation.message, Some("this is `int`".to_string()));
+    /// assert_eq!(annotation.note, Some("because `1` is an integer literal".to_string()));
+    ///
+    /// # use ara_source::source::Source;
+    /// # use ara_source::source::SourceKind;
+    /// # use ara_source::SourceMap;
+    /// # use ara_reporting::builder::ColorChoice;
+    /// # use ara_reporting::builder::ReportBuilder;
+    /// # use ara_reporting::issue::Issue;
+    /// # use ara_reporting::Report;
+    /// let source = SourceMap::new(vec![
+    ///     Source::new(SourceKind::Script, "main.ara", "$a = 1 + 'x';"),
+    /// ]);
+    ///
+    /// let report = Report::new().with_issue(
+    ///     Issue::error("E0001", "mismatched types")
+    ///         .with_annotation(Annotation::primary("main.ara", 9, 12).with_message("this is `string`"))
+    ///         .with_annotation(
+    ///             Annotation::secondary("main.ara", 5, 6)
+    ///                 .with_message("this is `int`")
+    ///                 .with_note("because `1` is an integer literal"),
+    ///         ),
+    /// );
+    ///
+    /// let output = ReportBuilder::new(&source)
+    ///     .with_colors(ColorChoice::Never)
+    ///     .as_string(&report)
+    ///     .unwrap();
+    ///
+    /// assert!(output.contains(
+    ///     "  |      this is `int`\n  |      because `1` is an integer literal\n"
+    /// ));
+    /// ```
+    #[must_use]
+    pub fn with_note<S: Into<String>>(mut self, note: S) -> Self {
+        self.note = Some(note.into());
+
+        self
+    }
+
+    /// Set the type of this annotation.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// use ara_reporting::annotation::{Annotation, AnnotationType};
+    ///
+    /// let annotation = Annotation::secondary("main.ara", 10, 1)
+    ///     .with_message("try removing this semicolon")
+    ///     .with_type(AnnotationType::Primary);
+    ///
+    /// assert_eq!(annotation.r#type, AnnotationType::Primary);
+    /// assert_eq!(annotation.message, Some("try removing this semicolon".to_string()));
+    /// ```
+    #[must_use]
+    pub fn with_type(mut self, r#type: AnnotationType) -> Self {
+        self.r#type = r#type;
+
+        self
+    }
+
+    /// Returns whether this annotation is a primary annotation.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// use ara_reporting::annotation::{Annotation, AnnotationType};
+    ///
+    /// let annotation = Annotation::secondary("main.ara", 10, 1);
+    /// assert!(!annotation.is_primary());
+    ///
+    /// let annotation = annotation.with_type(AnnotationType::Primary);
+    /// assert!(annotation.is_primary());
+    /// ```
+    pub fn is_primary(&self) -> bool {
+        self.r#type == AnnotationType::Primary
+    }
+
+    /// Returns whether this annotation points at the same span as the given one.
+    ///
+    /// Unlike `==`, this ignores the message and the type of both annotations.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// use ara_reporting::annotation::Annotation;
+    ///
+    /// let a = Annotation::secondary("main.ara", 10, 12).with_message("this is `int`");
+    /// let b = Annotation::primary("main.ara", 10, 12).with_message("found `int`");
+    /// let c = Annotation::secondary("main.ara", 10, 13).with_message("this is `int`");
+    ///
+    /// assert!(a.same_span(&b));
+    /// assert!(!a.same_span(&c));
+    /// ```
+    pub fn same_span(&self, other: &Annotation) -> bool {
+        self.origin == other.origin
+            && self.from == other.from
+            && self.to == other.to
+            && self.ranges == other.ranges
+    }
+
+    /// Returns whether any range `from..to` of this annotation contains the given byte.
+    ///
+    /// Ranges are half-open like `Issue::contains_byte`, so an empty range contains no byte.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// use ara_reporting::annotation::Annotation;
+    ///
+    /// let annotation = Annotation::secondary("main.ara", 10, 14);
+    ///
+    /// assert!(annotation.contains(10));
+    /// assert!(annotation.contains(13));
+    /// assert!(!annotation.contains(14));
+    /// assert!(!annotation.contains(9));
+    ///
+    /// assert!(!Annotation::secondary("main.ara", 10, 10).contains(10));
+    /// assert!(Annotation::multi("main.ara", vec![(0, 2), (20, 22)]).contains(21));
+    /// ```
+    pub fn contains(&self, byte: usize) -> bool {
+        self.spans().any(|span| range_contains(span, byte))
+    }
+
+    /// Returns whether any range of this annotation overlaps any range of the given one.
+    ///
+    /// Ranges are half-open like `Annotation::contains`, so ranges overlap when they share
+    /// at least one byte, and an empty range overlaps nothing. Annotations of different
+    /// origins never overlap.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// use ara_reporting::annotation::Annotation;
+    ///
+    /// let annotation = Annotation::secondary("main.ara", 10, 14);
+    ///
+    /// // nested
+    /// assert!(annotation.overlaps(&Annotation::primary("main.ara", 11, 12)));
+    /// assert!(Annotation::primary("main.ara", 11, 12).overlaps(&annotation));
+    /// // adjacent
+    /// assert!(!annotation.overlaps(&Annotation::primary("main.ara", 14, 20)));
+    /// assert!(!annotation.overlaps(&Annotation::primary("main.ara", 5, 10)));
+    /// // disjoint
+    /// assert!(!annotation.overlaps(&Annotation::primary("main.ara", 20, 30)));
+    /// // empty
+    /// assert!(!annotation.overlaps(&Annotation::primary("main.ara", 12, 12)));
+    /// assert!(!annotation.overlaps(&Annotation::primary("lib.ara", 10, 14)));
+    /// ```
+    pub fn overlaps(&self, other: &Annotation) -> bool {
+        self.origin == other.origin
+            && self
+                .spans()
+                .any(|span| other.spans().any(|other| ranges_overlap(span, other)))
+    }
+
+    /// Returns the ranges of this annotation, starting with `from..to`.
+    pub(crate) fn spans(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        std::iter::once((self.from, self.to)).chain(self.ranges.iter().copied())
+    }
 }
@@ -2,6 +2,7 @@ use codespan_reporting::diagnostic::Diagnostic;
 use codespan_reporting::diagnostic::Label;
 use codespan_reporting::diagnostic::LabelStyle;
 use codespan_reporting::files::Error as CodespanError;
+use codespan_reporting::files::Files;
 use codespan_reporting::files::SimpleFiles;
 use codespan_reporting::term::emit;
 use codespan_reporting::term::Chars;
@@ -9,19 +10,65 @@ use codespan_reporting::term::Config;
 use codespan_reporting::term::DisplayStyle as CodespanDisplayStyle;
 use codespan_reporting::term::Styles;
 use rustc_hash::FxHashMap;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::hash::BuildHasher;
+use std::io::IsTerminal;
+use std::io::Write;
+use std::ops::Range;
 use termcolor::BufferWriter;
 use termcolor::Color;
 use termcolor::ColorChoice as TermColorChoice;
+use termcolor::NoColor;
 use termcolor::StandardStream;
 use termcolor::WriteColor;
 
+use ara_source::source::Source;
+use ara_source::source::SourceKind;
+use ara_source::source::DEFAULT_NAME;
 use ara_source::SourceMap;
 
+use crate::annotation::Annotation;
 use crate::annotation::AnnotationType;
+use crate::checkstyle;
 use crate::error::Error;
+use crate::github;
+use crate::issue::Issue;
 use crate::issue::IssueSeverity;
+use crate::line_column;
+use crate::ranges_overlap;
+use crate::sarif;
+use crate::source_content;
+use crate::styled;
+use crate::styled::HtmlLine;
+use crate::styled::Role;
+use crate::styled::Style;
+use crate::styled::StyledLine;
+use crate::styled::StyledLinesWriter;
 use crate::Report;
+use crate::ReportFooter;
 use crate::Reportable;
+use crate::SummaryCounts;
+use crate::REPORT_SCHEMA_VERSION;
+
+const UNDERLINE_MARKERS: [char; 4] = ['-', '~', '=', '*'];
+
+/// The number of cells of the severity bar.
+const SEVERITY_BAR_WIDTH: usize = 20;
+
+/// The maximum number of lines between two close annotations of an annotation group.
+const ANNOTATION_GROUP_MAX_GAP: usize = 5;
+
+/// The source map of headless builders.
+static EMPTY_SOURCE_MAP: SourceMap = SourceMap {
+    sources: Vec::new(),
+};
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum AnnotationMessages {
+    Inline,
+    Footnotes,
+}
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub enum CharSet {
@@ -41,6 +88,72 @@ pub enum DisplayStyle {
     Default,
     Comfortable,
     Compact,
+    Inline,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum EncodingPolicy {
+    Lossy,
+    Strict,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum NoteNormalization {
+    None,
+    EnsureTrailingPeriod,
+    CapitalizeFirst,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum OutputFormat {
+    Human,
+    Json,
+    Ndjson,
+    RustcJson,
+    CodeQuality,
+    Sarif,
+    GitHub,
+    Checkstyle,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum SeverityPrefix {
+    Word,
+    Padded,
+    Symbol,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum SummaryOrder {
+    Ascending,
+    Descending,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Separator {
+    None,
+    BlankLine,
+    Rule(char),
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum SkipReason {
+    Hidden,
+    BelowThreshold,
+    OutsideRegion,
+    Truncated,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct RenderPlan {
+    pub rendered: Vec<Issue>,
+    pub skipped: Vec<(Issue, SkipReason)>,
+    pub footers: Vec<ReportFooter>,
+}
+
+pub struct OutputTarget<'w> {
+    pub format: OutputFormat,
+    pub writer: &'w mut dyn WriteColor,
 }
 
 #[derive(Debug, Clone)]
@@ -49,6 +162,36 @@ pub struct ReportBuilder<'a> {
     pub colors: ColorChoice,
     pub charset: CharSet,
     pub style: DisplayStyle,
+    pub compact_footer: bool,
+    pub separator: Separator,
+    pub styled_notes: bool,
+    pub global_note: Option<String>,
+    pub global_note_in_footer: bool,
+    pub distinct_underlines: bool,
+    pub encoding_policy: EncodingPolicy,
+    pub top: Option<usize>,
+    pub show_codes: bool,
+    pub origin_thresholds: Vec<(String, IssueSeverity)>,
+    pub note_normalization: NoteNormalization,
+    pub fold_groups: bool,
+    pub file_banners: bool,
+    pub max_output_bytes: Option<usize>,
+    pub summary_order: SummaryOrder,
+    pub region: Option<(String, usize, usize)>,
+    pub annotation_messages: AnnotationMessages,
+    pub source_offsets: Vec<(String, usize, usize)>,
+    pub wrap_code_frames: bool,
+    pub severity_prefix: SeverityPrefix,
+    pub show_ids: bool,
+    pub summary_prefix: Option<String>,
+    pub single_primary: bool,
+    pub config: Option<Config>,
+    pub config_styles: bool,
+    pub caret_legend: bool,
+    pub trim_trailing_whitespace: bool,
+    pub tab_width: usize,
+    pub start_context_lines: usize,
+    pub end_context_lines: usize,
 }
 
 /// A report builder.
@@ -73,19 +216,85 @@ pub struct ReportBuilder<'a> {
 /// let builder = ReportBuilder::new(&source);
 /// assert_eq!(builder.source_map.sources[0].content, "function main(): void {}");
 /// ```
-impl ReportBuilder<'_> {
+impl<'a> ReportBuilder<'a> {
     /// Create a new report builder.
-    pub fn new(source_map: &SourceMap) -> ReportBuilder {
+    pub fn new(source_map: &SourceMap) -> ReportBuilder<'_> {
         ReportBuilder {
             source_map,
             colors: ColorChoice::Auto,
             charset: CharSet::Ascii,
             style: DisplayStyle::Default,
+            compact_footer: false,
+            separator: Separator::None,
+            styled_notes: false,
+            global_note: None,
+            global_note_in_footer: false,
+            distinct_underlines: false,
+            encoding_policy: EncodingPolicy::Strict,
+            top: None,
+            show_codes: true,
+            origin_thresholds: vec![],
+            note_normalization: NoteNormalization::None,
+            fold_groups: false,
+            file_banners: false,
+            max_output_bytes: None,
+            summary_order: SummaryOrder::Ascending,
+            region: None,
+            annotation_messages: AnnotationMessages::Inline,
+            source_offsets: vec![],
+            wrap_code_frames: false,
+            severity_prefix: SeverityPrefix::Word,
+            show_ids: false,
+            summary_prefix: Some("summary:".to_string()),
+            single_primary: false,
+            config: None,
+            config_styles: true,
+            caret_legend: false,
+            trim_trailing_whitespace: false,
+            tab_width: 2,
+            start_context_lines: 1,
+            end_context_lines: 1,
         }
     }
 
+    /// Create a new report builder without sources, for reports without spans.
+    ///
+    /// Rendering an issue that has a span returns `Error::FileMissing`.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// # use ara_reporting::builder::ColorChoice;
+    /// # use ara_reporting::builder::ReportBuilder;
+    /// # use ara_reporting::error::Error;
+    /// # use ara_reporting::issue::Issue;
+    /// # use ara_reporting::Report;
+    /// let builder = ReportBuilder::headless().with_colors(ColorChoice::Never);
+    ///
+    /// let report = Report::new()
+    ///     .with_issue(Issue::error("E0001", "failed to read config").with_note("permission denied"));
+    ///
+    /// let output = builder.as_string(&report).unwrap();
+    /// assert_eq!(output, "error[E0001]: failed to read config\n = permission denied\n\n");
+    ///
+    /// let report = Report::new()
+    ///     .with_issue(Issue::error("E0002", "...").with_source("main.ara", 0, 8));
+    ///
+    /// assert!(matches!(builder.as_string(&report), Err(Error::FileMissing)));
+    /// ```
+    pub fn headless() -> ReportBuilder<'static> {
+        ReportBuilder::new(&EMPTY_SOURCE_MAP)
+    }
+
     /// Set the color choice.
     ///
+    /// With `ColorChoice::Auto`, colors are enabled or disabled by the first of:
+    ///
+    /// 1. `CLICOLOR_FORCE`, set to anything but `0`, enables colors.
+    /// 2. `NO_COLOR`, set to anything but an empty string, disables colors.
+    /// 3. `CLICOLOR`, set to `0`, disables colors.
+    /// 4. colors are enabled if the output is a terminal.
+    ///
     /// Example:
     ///
     /// ```rust
@@ -109,6 +318,25 @@ impl ReportBuilder<'_> {
     ///
     /// let builder = builder.with_colors(ColorChoice::Auto);
     /// assert_eq!(builder.colors, ColorChoice::Auto);
+    ///
+    /// # use ara_reporting::issue::Issue;
+    /// let report = Report::new().with_issue(Issue::error("E0001", "..."));
+    ///
+    /// // doc tests do not run in a terminal.
+    /// assert!(!builder.as_string(&report).unwrap().contains('\u{1b}'));
+    ///
+    /// std::env::set_var("CLICOLOR_FORCE", "1");
+    /// assert!(builder.as_string(&report).unwrap().contains('\u{1b}'));
+    ///
+    /// std::env::set_var("NO_COLOR", "1");
+    /// assert!(builder.as_string(&report).unwrap().contains('\u{1b}'));
+    ///
+    /// std::env::set_var("CLICOLOR_FORCE", "0");
+    /// assert!(!builder.as_string(&report).unwrap().contains('\u{1b}'));
+    ///
+    /// std::env::remove_var("NO_COLOR");
+    /// std::env::set_var("CLICOLOR", "0");
+    /// assert!(!builder.as_string(&report).unwrap().contains('\u{1b}'));
     /// ```
     #[must_use]
     pub fn with_colors(mut self, colors: ColorChoice) -> Self {
@@ -149,6 +377,9 @@ impl ReportBuilder<'_> {
 
     /// Set the display style
     ///
+    /// `DisplayStyle::Inline` renders each issue as a single header line, prefixed with its
+    /// location, followed by the source line and a caret line, without any border or context.
+    ///
     /// Example:
     ///
     /// ```rust
@@ -172,6 +403,25 @@ impl ReportBuilder<'_> {
     ///
     /// let builder = builder.with_style(DisplayStyle::Compact);
     /// assert_eq!(builder.style, DisplayStyle::Compact);
+    ///
+    /// let builder = builder.with_style(DisplayStyle::Inline);
+    /// assert_eq!(builder.style, DisplayStyle::Inline);
+    ///
+    /// # use ara_reporting::builder::ColorChoice;
+    /// # use ara_reporting::issue::Issue;
+    /// let source = SourceMap::new(vec![
+    ///     Source::new(SourceKind::Script, "main.ara", "function main(): void {\n    $b = match;\n}"),
+    /// ]);
+    /// let report = Report::new()
+    ///     .with_issue(Issue::error("E0417", "unexpected `match`").with_source("main.ara", 33, 38));
+    ///
+    /// let output = ReportBuilder::new(&source)
+    ///     .with_colors(ColorChoice::Never)
+    ///     .with_style(DisplayStyle::Inline)
+    ///     .as_string(&report)
+    ///     .unwrap();
+    ///
+    /// assert_eq!(output, "main.ara:2:10 error[E0417]: unexpected `match`\n    $b = match;\n         ^^^^^\n");
     /// ```
     #[must_use]
     pub fn with_style(mut self, style: DisplayStyle) -> Self {
@@ -180,191 +430,4062 @@ impl ReportBuilder<'_> {
         self
     }
 
-    /// Print the report to stdout.
-    pub fn print(&self, reportable: &dyn Reportable) -> Result<(), Error> {
-        let mut writer = StandardStream::stdout(match self.colors {
-            ColorChoice::Always => match self.charset {
-                CharSet::Ascii => TermColorChoice::AlwaysAnsi,
-                CharSet::Unicode => TermColorChoice::Always,
-            },
-            ColorChoice::Auto => TermColorChoice::Auto,
-            ColorChoice::Never => TermColorChoice::Never,
-        });
+    /// Set whether the footer should be rendered as plain lines instead of a framed diagnostic.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// # use ara_source::source::Source;
+    /// # use ara_source::source::SourceKind;
+    /// # use ara_source::SourceMap;
+    /// # use ara_reporting::builder::ReportBuilder;
+    /// # use ara_reporting::builder::ColorChoice;
+    /// # use ara_reporting::issue::Issue;
+    /// # use ara_reporting::Report;
+    /// # use ara_reporting::ReportFooter;
+    /// # let source = SourceMap::new(vec![
+    /// #     Source::new(SourceKind::Script, "main.ara", "function main(): void {}"),
+    /// # ]);
+    /// let report = Report::new()
+    ///     .with_issue(Issue::error("E0001", "...").with_source("main.ara", 9, 13))
+    ///     .with_footer(ReportFooter::new("build failed").with_note("this is a note"));
+    ///
+    /// let builder = ReportBuilder::new(&source)
+    ///     .with_colors(ColorChoice::Never)
+    ///     .with_compact_footer(true);
+    /// assert!(builder.compact_footer);
+    ///
+    /// let output = builder.as_string(&report).unwrap();
+    /// assert!(output.ends_with("build failed\n  = this is a note\n  = summary: 1 error(s)\n"));
+    /// assert!(!output.contains("error: build failed"));
+    /// ```
+    #[must_use]
+    pub fn with_compact_footer(mut self, enabled: bool) -> Self {
+        self.compact_footer = enabled;
 
-        self.write(&mut writer, reportable)
+        self
     }
 
-    /// Print the report to stderr.
-    pub fn eprint(&self, reportable: &dyn Reportable) -> Result<(), Error> {
-        let mut writer = StandardStream::stderr(match self.colors {
-            ColorChoice::Always => match self.charset {
-                CharSet::Ascii => TermColorChoice::AlwaysAnsi,
-                CharSet::Unicode => TermColorChoice::Always,
-            },
-            ColorChoice::Auto => TermColorChoice::Auto,
-            ColorChoice::Never => TermColorChoice::Never,
-        });
+    /// Set the separator emitted between consecutive issues.
+    ///
+    /// The separator is never emitted before the first issue, nor before the footer.
+    ///
+    /// `Separator::Rule` draws a line as wide as the terminal, as reported by the `COLUMNS`
+    /// environment variable, falling back to 80 columns.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// # use ara_source::source::Source;
+    /// # use ara_source::source::SourceKind;
+    /// # use ara_source::SourceMap;
+    /// # use ara_reporting::builder::ReportBuilder;
+    /// # use ara_reporting::builder::ColorChoice;
+    /// # use ara_reporting::builder::Separator;
+    /// # use ara_reporting::issue::Issue;
+    /// # use ara_reporting::Report;
+    /// # let source = SourceMap::new(vec![
+    /// #     Source::new(SourceKind::Script, "main.ara", "function main(): void {}"),
+    /// # ]);
+    /// let report = Report::new()
+    ///     .with_issue(Issue::error("E0001", "first"))
+    ///     .with_issue(Issue::error("E0002", "second"));
+    ///
+    /// let builder = ReportBuilder::new(&source)
+    ///     .with_colors(ColorChoice::Never)
+    ///     .with_issue_separator(Separator::Rule('-'));
+    /// assert_eq!(builder.separator, Separator::Rule('-'));
+    ///
+    /// let output = builder.as_string(&report).unwrap();
+    /// let lines = output.lines().collect::<Vec<_>>();
+    /// assert!(lines[0].starts_with("error[E0001]: first"));
+    /// assert!(lines[2].starts_with("-----"));
+    /// assert!(lines[3].starts_with("error[E0002]: second"));
+    /// ```
+    #[must_use]
+    pub fn with_issue_separator(mut self, separator: Separator) -> Self {
+        self.separator = separator;
 
-        self.write(&mut writer, reportable)
+        self
     }
 
-    /// Get the report as a string.
-    pub fn as_string(&self, reportable: &dyn Reportable) -> Result<String, Error> {
-        let buffer = BufferWriter::stderr(match self.colors {
-            ColorChoice::Always => match self.charset {
-                CharSet::Ascii => TermColorChoice::AlwaysAnsi,
-                CharSet::Unicode => TermColorChoice::Always,
-            },
-            ColorChoice::Auto => TermColorChoice::Auto,
-            ColorChoice::Never => TermColorChoice::Never,
-        });
+    /// Set whether code spans (text between backticks) in issue notes should be styled.
+    ///
+    /// Code spans are styled through the writer, so they are only colored when the output
+    /// supports colors, backticks are kept as-is.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// # use ara_source::SourceMap;
+    /// # use ara_reporting::builder::ReportBuilder;
+    /// # use ara_reporting::builder::ColorChoice;
+    /// # use ara_reporting::issue::Issue;
+    /// # use ara_reporting::Report;
+    /// # use ara_reporting::styled::Role;
+    /// # let source = SourceMap::new(vec![]);
+    /// let report = Report::new()
+    ///     .with_issue(Issue::error("E0001", "...").with_note("consider using `null` instead of `void`"));
+    ///
+    /// let builder = ReportBuilder::new(&source).with_styled_notes(true);
+    /// assert!(builder.styled_notes);
+    ///
+    /// let output = builder.clone().with_colors(ColorChoice::Never).as_string(&report).unwrap();
+    /// assert!(output.contains("= consider using `null` instead of `void`"));
+    ///
+    /// let output = builder.clone().with_colors(ColorChoice::Always).as_string(&report).unwrap();
+    /// assert!(output.contains("consider using `\u{1b}[0m\u{1b}[2m\u{1b}[3mnull\u{1b}[0m` instead of"));
+    ///
+    /// let lines = builder.to_render_lines(&report).unwrap();
+    /// let (_, style) = lines[1].iter().find(|(text, _)| text == "null").unwrap();
+    /// assert_eq!(style.role, Some(Role::NoteCode));
+    /// ```
+    #[must_use]
+    pub fn with_styled_notes(mut self, enabled: bool) -> Self {
+        self.styled_notes = enabled;
 
-        let mut buffer = buffer.buffer();
+        self
+    }
 
-        self.write(&mut buffer, reportable)?;
+    /// Set a note that is appended to the notes of every issue when rendering.
+    ///
+    /// The note is added after the issue's own notes, the report itself is left untouched.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// # use ara_source::SourceMap;
+    /// # use ara_reporting::builder::ReportBuilder;
+    /// # use ara_reporting::builder::ColorChoice;
+    /// # use ara_reporting::issue::Issue;
+    /// # use ara_reporting::Report;
+    /// # use ara_reporting::ReportFooter;
+    /// # let source = SourceMap::new(vec![]);
+    /// let report = Report::new()
+    ///     .with_issue(Issue::error("E0001", "...").with_note("this is a note"))
+    ///     .with_footer(ReportFooter::new("done").with_summary(false));
+    ///
+    /// let builder = ReportBuilder::new(&source)
+    ///     .with_colors(ColorChoice::Never)
+    ///     .with_global_note("see https://ara-lang.io/errors for details");
+    /// assert_eq!(builder.global_note, Some("see https://ara-lang.io/errors for details".to_string()));
+    ///
+    /// let output = builder.as_string(&report).unwrap();
+    /// assert!(output.contains("= this is a note\n = see https://ara-lang.io/errors for details\n"));
+    /// assert_eq!(output.matches("see https://ara-lang.io/errors").count(), 1);
+    /// assert!(report.issues[0].notes.len() == 1);
+    /// ```
+    #[must_use]
+    pub fn with_global_note<S: Into<String>>(mut self, note: S) -> Self {
+        self.global_note = Some(note.into());
 
-        Ok(String::from_utf8_lossy(buffer.as_slice()).to_string())
+        self
     }
 
-    /// Write the report to the given writer.
-    pub fn write<T: WriteColor>(&self, mut w: T, reportable: &dyn Reportable) -> Result<(), Error> {
-        let mut styles = Styles::default();
+    /// Set whether the global note should also be appended to the footer notes.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// # use ara_source::SourceMap;
+    /// # use ara_reporting::builder::ReportBuilder;
+    /// # use ara_reporting::builder::ColorChoice;
+    /// # use ara_reporting::issue::Issue;
+    /// # use ara_reporting::Report;
+    /// # use ara_reporting::ReportFooter;
+    /// # let source = SourceMap::new(vec![]);
+    /// let report = Report::new()
+    ///     .with_issue(Issue::error("E0001", "..."))
+    ///     .with_footer(ReportFooter::new("done"));
+    ///
+    /// let builder = ReportBuilder::new(&source)
+    ///     .with_colors(ColorChoice::Never)
+    ///     .with_global_note("see https://ara-lang.io/errors for details")
+    ///     .with_global_note_in_footer(true);
+    /// assert!(builder.global_note_in_footer);
+    ///
+    /// let output = builder.as_string(&report).unwrap();
+    /// assert_eq!(output.matches("see https://ara-lang.io/errors").count(), 2);
+    /// assert!(output.ends_with("= see https://ara-lang.io/errors for details\n = summary: 1 error(s)\n\n"));
+    /// ```
+    #[must_use]
+    pub fn with_global_note_in_footer(mut self, enabled: bool) -> Self {
+        self.global_note_in_footer = enabled;
 
-        styles.secondary_label.set_bold(true);
-        styles.line_number.set_fg(Some(Color::Ansi256(8)));
-        styles.source_border.set_fg(Some(Color::Ansi256(8)));
+        self
+    }
 
-        let config = Config {
-            display_style: match self.style {
-                DisplayStyle::Default => CodespanDisplayStyle::Rich,
-                DisplayStyle::Comfortable => CodespanDisplayStyle::Medium,
-                DisplayStyle::Compact => CodespanDisplayStyle::Short,
-            },
-            chars: match self.charset {
-                CharSet::Ascii => Chars::ascii(),
-                CharSet::Unicode => Chars::box_drawing(),
-            },
-            tab_width: 2,
-            styles,
-            start_context_lines: 1,
-            end_context_lines: 1,
-        };
+    /// Set whether secondary annotations sharing a line should be told apart in ASCII mode.
+    ///
+    /// When enabled, each colliding annotation is labelled with a distinct marker
+    /// (cycling through `-`, `~`, `=`, and `*`), and its message is moved to a legend
+    /// below the frame.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// # use ara_source::source::Source;
+    /// # use ara_source::source::SourceKind;
+    /// # use ara_source::SourceMap;
+    /// # use ara_reporting::annotation::Annotation;
+    /// # use ara_reporting::builder::ReportBuilder;
+    /// # use ara_reporting::builder::ColorChoice;
+    /// # use ara_reporting::issue::Issue;
+    /// # use ara_reporting::Report;
+    /// let source = SourceMap::new(vec![
+    ///     Source::new(SourceKind::Script, "main.ara", "$a = $b + $c;"),
+    /// ]);
+    ///
+    /// let report = Report::new().with_issue(
+    ///     Issue::error("E0001", "...")
+    ///         .with_annotation(Annotation::secondary("main.ara", 5, 7).with_message("this is `int`"))
+    ///         .with_annotation(Annotation::secondary("main.ara", 10, 12).with_message("this is `string`")),
+    /// );
+    ///
+    /// let builder = ReportBuilder::new(&source)
+    ///     .with_colors(ColorChoice::Never)
+    ///     .with_distinct_underlines(true);
+    /// assert!(builder.distinct_underlines);
+    ///
+    /// let output = builder.as_string(&report).unwrap();
+    /// assert!(output.contains("= [-] this is `int`"));
+    /// assert!(output.contains("= [~] this is `string`"));
+    /// ```
+    #[must_use]
+    pub fn with_distinct_underlines(mut self, enabled: bool) -> Self {
+        self.distinct_underlines = enabled;
 
-        let mut files = SimpleFiles::new();
-        let mut files_ids = FxHashMap::default();
-        self.source_map.sources.iter().for_each(|source| {
-            files_ids.insert(
-                source.name().to_string(),
-                files.add(source.name(), &source.content),
-            );
-        });
+        self
+    }
 
-        for report in reportable.to_reports() {
-            let diagnostics = self.diagnostics(report, &files_ids);
-
-            for diagnostic in diagnostics {
-                match emit(&mut w, &config, &files, &diagnostic) {
-                    Ok(_) => (),
-                    Err(err) => match err {
-                        CodespanError::FileMissing => Err(Error::FileMissing)?,
-                        CodespanError::IndexTooLarge { given, max } => {
-                            Err(Error::IndexTooLarge { given, max })?
-                        }
-                        CodespanError::LineTooLarge { given, max } => {
-                            Err(Error::LineTooLarge { given, max })?
-                        }
-                        CodespanError::ColumnTooLarge { given, max } => {
-                            Err(Error::ColumnTooLarge { given, max })?
-                        }
-                        CodespanError::InvalidCharBoundary { given } => {
-                            Err(Error::InvalidCharBoundary { given })?
-                        }
-                        CodespanError::Io(err) => Err(Error::Io(err))?,
-                        other => Err(Error::CodespanError(other))?,
-                    },
-                }
-            }
-        }
+    /// Set the policy used for spans that do not fall on UTF-8 character boundaries.
+    ///
+    /// Such spans usually come from offsets computed against content that was not valid UTF-8,
+    /// `EncodingPolicy::Strict` fails with `Error::InvalidSourceEncoding`, naming the offending
+    /// origin, while `EncodingPolicy::Lossy` widens the span to the surrounding characters so
+    /// that rendering can proceed.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// # use ara_source::source::Source;
+    /// # use ara_source::source::SourceKind;
+    /// # use ara_source::SourceMap;
+    /// # use ara_reporting::builder::ReportBuilder;
+    /// # use ara_reporting::builder::ColorChoice;
+    /// # use ara_reporting::builder::EncodingPolicy;
+    /// # use ara_reporting::error::Error;
+    /// # use ara_reporting::issue::Issue;
+    /// # use ara_reporting::Report;
+    /// let source = SourceMap::new(vec![
+    ///     Source::new(SourceKind::Script, "main.ara", "$café = 1;"),
+    /// ]);
+    ///
+    /// let report = Report::new().with_issue(Issue::error("E0001", "...").with_source("main.ara", 1, 5));
+    ///
+    /// let builder = ReportBuilder::new(&source).with_colors(ColorChoice::Never);
+    /// assert_eq!(builder.encoding_policy, EncodingPolicy::Strict);
+    ///
+    /// match builder.as_string(&report) {
+    ///     Err(Error::InvalidSourceEncoding { origin, given }) => {
+    ///         assert_eq!(origin, "main.ara");
+    ///         assert_eq!(given, 5);
+    ///     }
+    ///     _ => unreachable!(),
+    /// }
+    ///
+    /// let builder = builder.with_encoding_policy(EncodingPolicy::Lossy);
+    /// assert_eq!(builder.encoding_policy, EncodingPolicy::Lossy);
+    ///
+    /// let output = builder.as_string(&report).unwrap();
+    /// assert!(output.contains("$café = 1;"));
+    /// ```
+    #[must_use]
+    pub fn with_encoding_policy(mut self, policy: EncodingPolicy) -> Self {
+        self.encoding_policy = policy;
 
-        Ok(())
+        self
     }
 
-    fn diagnostics(
-        &self,
-        report: &Report,
-        files_ids: &FxHashMap<String, usize>,
-    ) -> Vec<Diagnostic<usize>> {
-        let mut diagnostics = Vec::new();
-
-        for issue in &report.issues {
-            let mut diagnostic = Diagnostic::new(issue.severity.into())
-                .with_message(&issue.message)
-                .with_notes(issue.notes.clone())
-                .with_labels(
-                    issue
-                        .annotations
-                        .iter()
-                        .map(|annotation| {
-                            let mut label = Label::new(
-                                match annotation.r#type {
-                                    AnnotationType::Primary => LabelStyle::Primary,
-                                    AnnotationType::Secondary => LabelStyle::Secondary,
-                                },
-                                *files_ids.get(&annotation.origin).unwrap_or(&0),
-                                annotation.from..annotation.to,
-                            );
-
-                            if let Some(message) = &annotation.message {
-                                label = label.with_message(message);
-                            }
-
-                            label
-                        })
-                        .collect(),
-                );
+    /// Set the number of most severe issues to render per report.
+    ///
+    /// Issues are rendered from the most to the least severe, then from the highest to the
+    /// lowest weight, ties are kept in document order, and the remaining issues are omitted,
+    /// while still being counted in the footer summary.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// # use ara_source::SourceMap;
+    /// # use ara_reporting::builder::ReportBuilder;
+    /// # use ara_reporting::builder::ColorChoice;
+    /// # use ara_reporting::issue::Issue;
+    /// # use ara_reporting::Report;
+    /// # use ara_reporting::ReportFooter;
+    /// # let source = SourceMap::new(vec![]);
+    /// let report = Report::new()
+    ///     .with_issue(Issue::note("N0001", "first note"))
+    ///     .with_issue(Issue::error("E0001", "first error"))
+    ///     .with_issue(Issue::warning("W0001", "first warning"))
+    ///     .with_issue(Issue::error("E0002", "second error"))
+    ///     .with_footer(ReportFooter::new("done"));
+    ///
+    /// let builder = ReportBuilder::new(&source)
+    ///     .with_colors(ColorChoice::Never)
+    ///     .with_top(Some(2));
+    /// assert_eq!(builder.top, Some(2));
+    ///
+    /// let output = builder.as_string(&report).unwrap();
+    /// let first = output.find("error[E0001]: first error").unwrap();
+    /// let second = output.find("error[E0002]: second error").unwrap();
+    /// assert!(first < second);
+    /// assert!(!output.contains("first note"));
+    /// assert!(!output.contains("first warning"));
+    /// assert!(output.contains("… 2 lower-severity issues hidden"));
+    /// assert!(output.contains("summary: 1 note(s), 1 warning(s), 2 error(s)"));
+    /// ```
+    #[must_use]
+    pub fn with_top(mut self, top: Option<usize>) -> Self {
+        self.top = top;
 
-            if let Some(code) = &issue.code {
-                diagnostic = diagnostic.with_code(code);
-            }
+        self
+    }
 
-            if let Some((source, from, to)) = &issue.source {
-                diagnostic = diagnostic.with_labels(vec![Label::primary(
-                    *files_ids.get(source).unwrap_or(&0),
-                    *from..*to,
-                )])
-            }
+    /// Set whether issue codes should be shown in the rendered headers.
+    ///
+    /// This only affects rendering, issue codes are kept in the report data.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// # use ara_source::SourceMap;
+    /// # use ara_reporting::builder::ReportBuilder;
+    /// # use ara_reporting::builder::ColorChoice;
+    /// # use ara_reporting::issue::Issue;
+    /// # use ara_reporting::Report;
+    /// # let source = SourceMap::new(vec![]);
+    /// let report = Report::new()
+    ///     .with_issue(Issue::error("E0417", "`match` arms have incompatible types"));
+    ///
+    /// let builder = ReportBuilder::new(&source)
+    ///     .with_colors(ColorChoice::Never)
+    ///     .with_show_codes(false);
+    /// assert!(!builder.show_codes);
+    ///
+    /// let output = builder.as_string(&report).unwrap();
+    /// assert!(output.starts_with("error: `match` arms have incompatible types"));
+    /// assert!(!output.contains("[E0417]"));
+    ///
+    /// let json = serde_json::to_string(&report).unwrap();
+    /// assert!(json.contains(r#""code":"E0417""#));
+    /// ```
+    #[must_use]
+    pub fn with_show_codes(mut self, enabled: bool) -> Self {
+        self.show_codes = enabled;
+
+        self
+    }
+
+    /// Set whether issue ids should be rendered in issue headers.
+    ///
+    /// Ids are rendered after the severity and code, as `#` followed by their first eight
+    /// hexadecimal digits, issues without an id are rendered as usual, see
+    /// `Report::assign_ids`.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// # use ara_source::SourceMap;
+    /// # use ara_reporting::builder::ReportBuilder;
+    /// # use ara_reporting::builder::ColorChoice;
+    /// # use ara_reporting::issue::Issue;
+    /// # use ara_reporting::Report;
+    /// # let source = SourceMap::new(vec![]);
+    /// let mut report = Report::new()
+    ///     .with_issue(Issue::error("E0417", "`match` arms have incompatible types"));
+    /// report.assign_ids();
+    ///
+    /// let builder = ReportBuilder::new(&source)
+    ///     .with_colors(ColorChoice::Never)
+    ///     .with_show_ids(true);
+    /// assert!(builder.show_ids);
+    ///
+    /// let id = report.issues[0].id.unwrap();
+    /// let output = builder.as_string(&report).unwrap();
+    /// assert!(output.starts_with(&format!(
+    ///     "error[E0417] (#{:08x}): `match` arms have incompatible types",
+    ///     id >> 32,
+    /// )));
+    /// ```
+    #[must_use]
+    pub fn with_show_ids(mut self, enabled: bool) -> Self {
+        self.show_ids = enabled;
+
+        self
+    }
+
+    /// Set the minimum severity of issues rendered for origins matching the given glob.
+    ///
+    /// In globs, `*` matches any sequence of characters except `/`, `**` matches any sequence
+    /// of characters, and `?` matches a single character. When multiple rules match an origin,
+    /// the most specific one applies.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// # use ara_source::source::Source;
+    /// # use ara_source::source::SourceKind;
+    /// # use ara_source::SourceMap;
+    /// # use ara_reporting::builder::ReportBuilder;
+    /// # use ara_reporting::builder::ColorChoice;
+    /// # use ara_reporting::issue::Issue;
+    /// # use ara_reporting::issue::IssueSeverity;
+    /// # use ara_reporting::Report;
+    /// let source = SourceMap::new(vec![
+    ///     Source::new(SourceKind::Script, "src/main.ara", "function main(): void {}"),
+    ///     Source::new(SourceKind::Script, "vendor/lib/foo.ara", "function foo(): void {}"),
+    ///     Source::new(SourceKind::Script, "vendor/ara/bar.ara", "function bar(): void {}"),
+    /// ]);
+    ///
+    /// let report = Report::new()
+    ///     .with_issue(Issue::note("N0001", "src note").with_source("src/main.ara", 9, 13))
+    ///     .with_issue(Issue::note("N0002", "vendor note").with_source("vendor/lib/foo.ara", 9, 12))
+    ///     .with_issue(Issue::warning("W0001", "vendor warning").with_source("vendor/lib/foo.ara", 9, 12))
+    ///     .with_issue(Issue::note("N0003", "ara note").with_source("vendor/ara/bar.ara", 9, 12));
+    ///
+    /// let builder = ReportBuilder::new(&source)
+    ///     .with_colors(ColorChoice::Never)
+    ///     .with_origin_min_severity("vendor/**", IssueSeverity::Warning)
+    ///     .with_origin_min_severity("vendor/ara/*.ara", IssueSeverity::Note);
+    ///
+    /// let output = builder.as_string(&report).unwrap();
+    /// assert!(output.contains("src note"));
+    /// assert!(!output.contains("vendor note"));
+    /// assert!(output.contains("vendor warning"));
+    /// assert!(output.contains("ara note"));
+    /// ```
+    #[must_use]
+    pub fn with_origin_min_severity<G: Into<String>>(
+        mut self,
+        glob: G,
+        min: IssueSeverity,
+    ) -> Self {
+        self.origin_thresholds.push((glob.into(), min));
+
+        self
+    }
+
+    /// Set the normalization applied to issue and footer notes when rendering.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// # use ara_source::SourceMap;
+    /// # use ara_reporting::builder::ReportBuilder;
+    /// # use ara_reporting::builder::ColorChoice;
+    /// # use ara_reporting::builder::NoteNormalization;
+    /// # use ara_reporting::issue::Issue;
+    /// # use ara_reporting::Report;
+    /// # use ara_reporting::ReportFooter;
+    /// # let source = SourceMap::new(vec![]);
+    /// let report = Report::new()
+    ///     .with_issue(
+    ///         Issue::error("E0001", "...")
+    ///             .with_note("consider using `null`")
+    ///             .with_note("this is fine.")
+    ///             .with_note("really!")
+    ///             .with_note("is it?"),
+    ///     )
+    ///     .with_footer(ReportFooter::new("done").with_note("footer note").with_summary(false));
+    ///
+    /// let builder = ReportBuilder::new(&source).with_colors(ColorChoice::Never);
+    /// assert_eq!(builder.note_normalization, NoteNormalization::None);
+    ///
+    /// let output = builder.clone().as_string(&report).unwrap();
+    /// assert!(output.contains("= consider using `null`\n"));
+    /// assert!(output.contains("= footer note\n"));
+    ///
+    /// let output = builder
+    ///     .clone()
+    ///     .with_note_normalization(NoteNormalization::EnsureTrailingPeriod)
+    ///     .as_string(&report)
+    ///     .unwrap();
+    /// assert!(output.contains("= consider using `null`.\n"));
+    /// assert!(output.contains("= this is fine.\n"));
+    /// assert!(output.contains("= really!\n"));
+    /// assert!(output.contains("= is it?\n"));
+    /// assert!(output.contains("= footer note.\n"));
+    ///
+    /// let output = builder
+    ///     .with_note_normalization(NoteNormalization::CapitalizeFirst)
+    ///     .as_string(&report)
+    ///     .unwrap();
+    /// assert!(output.contains("= Consider using `null`\n"));
+    /// assert!(output.contains("= This is fine.\n"));
+    /// assert!(output.contains("= Really!\n"));
+    /// assert!(output.contains("= Is it?\n"));
+    /// assert!(output.contains("= Footer note\n"));
+    /// ```
+    #[must_use]
+    pub fn with_note_normalization(mut self, normalization: NoteNormalization) -> Self {
+        self.note_normalization = normalization;
+
+        self
+    }
+
+    /// Set whether the workflow commands of each origin should be wrapped in GitHub Actions
+    /// fold groups, see `ReportBuilder::as_workflow_commands`.
+    ///
+    /// Grouping reorders the commands of each report by origin, in the order each origin is
+    /// first seen. Commands without an origin are grouped under `<unknown>`. Group titles are
+    /// escaped like workflow command messages. This has no effect on the other output formats.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// # use ara_source::source::Source;
+    /// # use ara_source::source::SourceKind;
+    /// # use ara_source::SourceMap;
+    /// # use ara_reporting::builder::ReportBuilder;
+    /// # use ara_reporting::builder::ColorChoice;
+    /// # use ara_reporting::issue::Issue;
+    /// # use ara_reporting::Report;
+    /// let source = SourceMap::new(vec![
+    ///     Source::new(SourceKind::Script, "main.ara", "function main(): void {}"),
+    ///     Source::new(SourceKind::Script, "lib.ara", "function lib(): void {}"),
+    /// ]);
+    ///
+    /// let report = Report::new()
+    ///     .with_issue(Issue::error("E0001", "...").with_source("main.ara", 0, 0))
+    ///     .with_issue(Issue::error("E0002", "...").with_source("lib.ara", 0, 0))
+    ///     .with_issue(Issue::error("E0003", "...").with_source("main.ara", 0, 0));
+    ///
+    /// let builder = ReportBuilder::new(&source)
+    ///     .with_colors(ColorChoice::Never)
+    ///     .with_fold_groups(true);
+    /// assert!(builder.fold_groups);
+    ///
+    /// let commands = builder.as_workflow_commands(&report).unwrap();
+    /// assert_eq!(commands.lines().collect::<Vec<_>>(), vec![
+    ///     "::group::main.ara",
+    ///     "::error file=main.ara,line=1,col=1,endLine=1,endColumn=1,title=E0001::...",
+    ///     "::error file=main.ara,line=1,col=1,endLine=1,endColumn=1,title=E0003::...",
+    ///     "::endgroup::",
+    ///     "::group::lib.ara",
+    ///     "::error file=lib.ara,line=1,col=1,endLine=1,endColumn=1,title=E0002::...",
+    ///     "::endgroup::",
+    /// ]);
+    ///
+    /// let output = builder.as_string(&report).unwrap();
+    /// assert!(!output.contains("::group::"));
+    /// ```
+    #[must_use]
+    pub fn with_fold_groups(mut self, enabled: bool) -> Self {
+        self.fold_groups = enabled;
+
+        self
+    }
+
+    /// Set whether issues should be grouped by origin, each group starting with a banner
+    /// line showing the origin and its number of issues.
+    ///
+    /// Issues without an origin are grouped together, without a banner.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// # use ara_source::source::Source;
+    /// # use ara_source::source::SourceKind;
+    /// # use ara_source::SourceMap;
+    /// # use ara_reporting::builder::CharSet;
+    /// # use ara_reporting::builder::ColorChoice;
+    /// # use ara_reporting::builder::DisplayStyle;
+    /// # use ara_reporting::builder::ReportBuilder;
+    /// # use ara_reporting::issue::Issue;
+    /// # use ara_reporting::Report;
+    /// let source = SourceMap::new(vec![
+    ///     Source::new(SourceKind::Script, "main.ara", "function main(): void {}"),
+    ///     Source::new(SourceKind::Script, "lib.ara", "function lib(): void {}"),
+    /// ]);
+    ///
+    /// let report = Report::new()
+    ///     .with_issue(Issue::error("E0001", "...").with_source("main.ara", 0, 0))
+    ///     .with_issue(Issue::error("E0002", "...").with_source("lib.ara", 0, 0))
+    ///     .with_issue(Issue::error("E0003", "...").with_source("main.ara", 0, 0))
+    ///     .with_issue(Issue::error("E0004", "..."));
+    ///
+    /// let builder = ReportBuilder::new(&source)
+    ///     .with_colors(ColorChoice::Never)
+    ///     .with_style(DisplayStyle::Compact)
+    ///     .with_file_banners(true);
+    /// assert!(builder.file_banners);
+    ///
+    /// let output = builder.as_string(&report).unwrap();
+    /// let lines = output.lines().filter(|line| !line.is_empty()).collect::<Vec<_>>();
+    /// assert_eq!(lines, vec![
+    ///     "== main.ara - 2 issue(s)",
+    ///     "main.ara:1:1: error[E0001]: ...",
+    ///     "main.ara:1:1: error[E0003]: ...",
+    ///     "== lib.ara - 1 issue(s)",
+    ///     "lib.ara:1:1: error[E0002]: ...",
+    ///     "error[E0004]: ...",
+    /// ]);
+    ///
+    /// let output = builder.with_charset(CharSet::Unicode).as_string(&report).unwrap();
+    /// assert!(output.starts_with("━━ main.ara — 2 issue(s)\n"));
+    /// ```
+    #[must_use]
+    pub fn with_file_banners(mut self, enabled: bool) -> Self {
+        self.file_banners = enabled;
+
+        self
+    }
+
+    /// Set the label written before the footer summary.
+    ///
+    /// The label is separated from the summary by a space, `None` omits it entirely.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// # use ara_source::SourceMap;
+    /// # use ara_reporting::builder::ReportBuilder;
+    /// # use ara_reporting::builder::ColorChoice;
+    /// # use ara_reporting::issue::Issue;
+    /// # use ara_reporting::Report;
+    /// # use ara_reporting::ReportFooter;
+    /// # let source = SourceMap::new(vec![]);
+    /// let report = Report::new()
+    ///     .with_issue(Issue::error("E0001", "..."))
+    ///     .with_footer(ReportFooter::new("done"));
+    ///
+    /// let builder = ReportBuilder::new(&source).with_colors(ColorChoice::Never);
+    /// assert_eq!(builder.summary_prefix, Some("summary:".to_string()));
+    /// assert!(builder.as_string(&report).unwrap().contains(" = summary: 1 error(s)\n"));
+    ///
+    /// let builder = builder.with_summary_prefix(Some("Totals:".to_string()));
+    /// assert!(builder.as_string(&report).unwrap().contains(" = Totals: 1 error(s)\n"));
+    ///
+    /// let builder = builder.with_summary_prefix(None);
+    /// assert!(builder.as_string(&report).unwrap().contains(" = 1 error(s)\n"));
+    /// ```
+    #[must_use]
+    pub fn with_summary_prefix(mut self, prefix: Option<String>) -> Self {
+        self.summary_prefix = prefix;
+
+        self
+    }
+
+    /// Set the maximum number of bytes written when rendering.
+    ///
+    /// Rendering stops at the last issue that fits within the limit, no issue is ever cut
+    /// in half, and a trailing line reports the number of omitted bytes. Only text is
+    /// counted, not the escape sequences of colors.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// # use ara_source::SourceMap;
+    /// # use ara_reporting::builder::ReportBuilder;
+    /// # use ara_reporting::builder::ColorChoice;
+    /// # use ara_reporting::issue::Issue;
+    /// # use ara_reporting::Report;
+    /// # use ara_reporting::styled::Role;
+    /// # let source = SourceMap::new(vec![]);
+    /// let report = Report::new()
+    ///     .with_issue(Issue::error("E0001", "first"))
+    ///     .with_issue(Issue::error("E0002", "second"))
+    ///     .with_issue(Issue::error("E0003", "third"));
+    ///
+    /// let builder = ReportBuilder::new(&source)
+    ///     .with_colors(ColorChoice::Never)
+    ///     .with_max_output_bytes(Some(50));
+    /// assert_eq!(builder.max_output_bytes, Some(50));
+    ///
+    /// let output = builder.as_string(&report).unwrap();
+    /// assert_eq!(output, "error[E0001]: first\n\nerror[E0002]: second\n\n… output truncated (21 bytes omitted)\n");
+    ///
+    /// let lines = builder.to_render_lines(&report).unwrap();
+    /// assert_eq!(lines[0][0].1.role, Some(Role::HeaderError));
+    /// ```
+    #[must_use]
+    pub fn with_max_output_bytes(mut self, max: Option<usize>) -> Self {
+        self.max_output_bytes = max;
+
+        self
+    }
+
+    /// Set the order of the severity entries in the footer summary.
+    ///
+    /// `SummaryOrder::Ascending` lists the least severe entries first, while
+    /// `SummaryOrder::Descending` lists the most severe entries first.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// # use ara_source::SourceMap;
+    /// # use ara_reporting::builder::ReportBuilder;
+    /// # use ara_reporting::builder::ColorChoice;
+    /// # use ara_reporting::builder::SummaryOrder;
+    /// # use ara_reporting::issue::Issue;
+    /// # use ara_reporting::Report;
+    /// # use ara_reporting::ReportFooter;
+    /// # let source = SourceMap::new(vec![]);
+    /// let report = Report::new()
+    ///     .with_issue(Issue::note("N0001", "..."))
+    ///     .with_issue(Issue::error("E0001", "..."))
+    ///     .with_issue(Issue::bug("B0001", "..."))
+    ///     .with_footer(ReportFooter::new("done"));
+    ///
+    /// let builder = ReportBuilder::new(&source).with_colors(ColorChoice::Never);
+    /// assert_eq!(builder.summary_order, SummaryOrder::Ascending);
+    ///
+    /// let output = builder.clone().as_string(&report).unwrap();
+    /// assert!(output.contains("summary: 1 note(s), 1 error(s), 1 bug(s)"));
+    ///
+    /// let output = builder
+    ///     .with_summary_order(SummaryOrder::Descending)
+    ///     .as_string(&report)
+    ///     .unwrap();
+    /// assert!(output.contains("summary: 1 bug(s), 1 error(s), 1 note(s)"));
+    /// ```
+    #[must_use]
+    pub fn with_summary_order(mut self, order: SummaryOrder) -> Self {
+        self.summary_order = order;
+
+        self
+    }
+
+    /// Only render issues whose source overlaps the given region.
+    ///
+    /// Issues in other origins, outside of the region, or without a source are skipped,
+    /// and counted in a footer note.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// # use ara_source::source::Source;
+    /// # use ara_source::source::SourceKind;
+    /// # use ara_source::SourceMap;
+    /// # use ara_reporting::builder::ReportBuilder;
+    /// # use ara_reporting::builder::ColorChoice;
+    /// # use ara_reporting::issue::Issue;
+    /// # use ara_reporting::Report;
+    /// # use ara_reporting::ReportFooter;
+    /// let source = SourceMap::new(vec![
+    ///     Source::new(SourceKind::Script, "main.ara", "function main(): void {}"),
+    ///     Source::new(SourceKind::Script, "lib.ara", "function lib(): void {}"),
+    /// ]);
+    ///
+    /// let report = Report::new()
+    ///     .with_issue(Issue::error("E0001", "inside").with_source("main.ara", 9, 13))
+    ///     .with_issue(Issue::error("E0002", "outside").with_source("main.ara", 17, 21))
+    ///     .with_issue(Issue::error("E0003", "other file").with_source("lib.ara", 9, 12))
+    ///     .with_footer(ReportFooter::new("done"));
+    ///
+    /// let builder = ReportBuilder::new(&source)
+    ///     .with_colors(ColorChoice::Never)
+    ///     .with_region_filter("main.ara", 0, 15);
+    /// assert_eq!(builder.region, Some(("main.ara".to_string(), 0, 15)));
+    ///
+    /// let output = builder.as_string(&report).unwrap();
+    /// assert!(output.contains("error[E0001]: inside"));
+    /// assert!(!output.contains("error[E0002]: outside"));
+    /// assert!(!output.contains("error[E0003]: other file"));
+    /// assert!(output.contains("= 2 issue(s) outside of the region skipped"));
+    /// ```
+    #[must_use]
+    pub fn with_region_filter<O: Into<String>>(
+        mut self,
+        origin: O,
+        from: usize,
+        to: usize,
+    ) -> Self {
+        self.region = Some((origin.into(), from, to));
+
+        self
+    }
+
+    /// Set where annotation messages are rendered.
+    ///
+    /// `AnnotationMessages::Inline` renders messages next to their annotations, while
+    /// `AnnotationMessages::Footnotes` replaces them with numbered markers, and lists the
+    /// messages below the frame.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// # use ara_source::source::Source;
+    /// # use ara_source::source::SourceKind;
+    /// # use ara_source::SourceMap;
+    /// # use ara_reporting::annotation::Annotation;
+    /// # use ara_reporting::builder::AnnotationMessages;
+    /// # use ara_reporting::builder::ReportBuilder;
+    /// # use ara_reporting::builder::ColorChoice;
+    /// # use ara_reporting::issue::Issue;
+    /// # use ara_reporting::Report;
+    /// let source = SourceMap::new(vec![
+    ///     Source::new(SourceKind::Script, "main.ara", "$a = $b + $c;"),
+    /// ]);
+    ///
+    /// let report = Report::new().with_issue(
+    ///     Issue::error("E0001", "...")
+    ///         .with_annotation(Annotation::secondary("main.ara", 5, 7).with_message("this is `int`"))
+    ///         .with_annotation(Annotation::secondary("main.ara", 8, 9))
+    ///         .with_annotation(Annotation::secondary("main.ara", 10, 12).with_message("this is `string`"))
+    ///         .with_note("this is a note"),
+    /// );
+    ///
+    /// let builder = ReportBuilder::new(&source).with_colors(ColorChoice::Never);
+    /// assert_eq!(builder.annotation_messages, AnnotationMessages::Inline);
+    ///
+    /// let builder = builder.with_annotation_messages(AnnotationMessages::Footnotes);
+    /// assert_eq!(builder.annotation_messages, AnnotationMessages::Footnotes);
+    ///
+    /// let output = builder.as_string(&report).unwrap();
+    /// assert!(output.contains("= this is a note\n  = [1] this is `int`\n  = [2] this is `string`\n"));
+    /// assert_eq!(output.matches("this is `int`").count(), 1);
+    /// ```
+    #[must_use]
+    pub fn with_annotation_messages(mut self, placement: AnnotationMessages) -> Self {
+        self.annotation_messages = placement;
+
+        self
+    }
+
+    /// Set the offset of a source that is a fragment of a larger document.
+    ///
+    /// Issue spans remain relative to the fragment, while `line_offset` is added to the
+    /// rendered line numbers, and `byte_offset` is added to the byte offsets of
+    /// machine-readable output, so that both refer to the original document.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// # use ara_source::source::Source;
+    /// # use ara_source::source::SourceKind;
+    /// # use ara_source::SourceMap;
+    /// # use ara_reporting::builder::ReportBuilder;
+    /// # use ara_reporting::builder::ColorChoice;
+    /// # use ara_reporting::issue::Issue;
+    /// # use ara_reporting::Report;
+    /// // a snippet extracted from a markdown fence, starting at line 42 of `README.md`.
+    /// let source = SourceMap::new(vec![
+    ///     Source::new(SourceKind::Script, "README.md", "function main(): void {\n  $a = 1;\n}"),
+    /// ]);
+    ///
+    /// let report = Report::new()
+    ///     .with_issue(Issue::warning("W0001", "unused variable `$a`").with_source("README.md", 26, 28));
+    ///
+    /// let builder = ReportBuilder::new(&source)
+    ///     .with_colors(ColorChoice::Never)
+    ///     .with_source_offset("README.md", 41, 1024);
+    /// assert_eq!(builder.source_offsets, vec![("README.md".to_string(), 41, 1024)]);
+    ///
+    /// let output = builder.as_string(&report).unwrap();
+    /// assert!(output.contains("--> README.md:43:3"));
+    /// assert!(output.contains("43 |   $a = 1;"));
+    ///
+    /// let mut output = vec![];
+    /// builder.write_ndjson(&mut output, &report).unwrap();
+    ///
+    /// let issue: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    /// assert_eq!(issue["line"], 43);
+    /// assert_eq!(issue["source"], serde_json::json!(["README.md", 1050, 1052]));
+    /// ```
+    #[must_use]
+    pub fn with_source_offset<O: Into<String>>(
+        mut self,
+        origin: O,
+        line_offset: usize,
+        byte_offset: usize,
+    ) -> Self {
+        let origin = origin.into();
+
+        self.source_offsets.retain(|(other, _, _)| *other != origin);
+        self.source_offsets.push((origin, line_offset, byte_offset));
+
+        self
+    }
+
+    /// Enable or disable soft-wrapping source lines of code frames at the terminal width.
+    ///
+    /// The width is read from the `COLUMNS` environment variable, defaulting to 80 columns.
+    /// Annotation underlines are split along with their source line, and continue onto the
+    /// wrapped continuation lines.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// # use ara_source::source::Source;
+    /// # use ara_source::source::SourceKind;
+    /// # use ara_source::SourceMap;
+    /// # use ara_reporting::builder::ReportBuilder;
+    /// # use ara_reporting::builder::ColorChoice;
+    /// # use ara_reporting::issue::Issue;
+    /// # use ara_reporting::Report;
+    /// # use ara_reporting::annotation::Annotation;
+    /// let code = format!("function main(): void {{ $value = {}; }}", "1 + ".repeat(30) + "1");
+    /// let source = SourceMap::new(vec![Source::new(SourceKind::Script, "main.ara", &code)]);
+    ///
+    /// let report = Report::new().with_issue(
+    ///     Issue::warning("W0001", "unused variable").with_annotation(
+    ///         Annotation::primary("main.ara", 24, 152).with_message("value is never used"),
+    ///     ),
+    /// );
+    ///
+    /// let builder = ReportBuilder::new(&source)
+    ///     .with_colors(ColorChoice::Never)
+    ///     .with_wrap_code_frames(true);
+    /// assert!(builder.wrap_code_frames);
+    ///
+    /// let output = builder.as_string(&report).unwrap();
+    /// assert!(output.contains(&format!(
+    ///     "1 | function main(): void {{ $value = {}1 +\n  | {}{}\n",
+    ///     "1 + ".repeat(10),
+    ///     " ".repeat(24),
+    ///     "^".repeat(52),
+    /// )));
+    /// assert!(output.contains(&format!(
+    ///     "  |  {}1 +\n  | {} value is never used\n  |  1; }}\n",
+    ///     "1 + ".repeat(18),
+    ///     "^".repeat(76),
+    /// )));
+    /// ```
+    #[must_use]
+    pub fn with_wrap_code_frames(mut self, enabled: bool) -> Self {
+        self.wrap_code_frames = enabled;
+
+        self
+    }
+
+    /// Set how the severity is written at the start of inline issue headers.
+    ///
+    /// `SeverityPrefix::Padded` pads severity words to the width of the longest one, while
+    /// `SeverityPrefix::Symbol` replaces them with fixed-width symbols (`[E]`, `[W]`, `[N]`,
+    /// `[H]`, and `[B]`), keeping columns aligned in logs.
+    ///
+    /// This applies to `DisplayStyle::Inline` and `DisplayStyle::Compact`. Headers of other
+    /// styles are rendered by `codespan-reporting`, and `Issue` is displayed without a
+    /// builder, so both are always written with the severity word.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// # use ara_source::source::Source;
+    /// # use ara_source::source::SourceKind;
+    /// # use ara_source::SourceMap;
+    /// # use ara_reporting::builder::ReportBuilder;
+    /// # use ara_reporting::builder::ColorChoice;
+    /// # use ara_reporting::builder::DisplayStyle;
+    /// # use ara_reporting::builder::SeverityPrefix;
+    /// # use ara_reporting::issue::Issue;
+    /// # use ara_reporting::Report;
+    /// # let source = SourceMap::new(vec![]);
+    /// let report = Report::new()
+    ///     .with_issue(Issue::error("E0001", "first"))
+    ///     .with_issue(Issue::warning("W0001", "second"));
+    ///
+    /// let builder = ReportBuilder::new(&source)
+    ///     .with_colors(ColorChoice::Never)
+    ///     .with_style(DisplayStyle::Inline);
+    /// assert_eq!(builder.severity_prefix, SeverityPrefix::Word);
+    /// assert_eq!(
+    ///     builder.as_string(&report).unwrap(),
+    ///     "error[E0001]: first\nwarning[W0001]: second\n",
+    /// );
+    ///
+    /// let builder = builder.with_severity_prefix(SeverityPrefix::Padded);
+    /// assert_eq!(
+    ///     builder.as_string(&report).unwrap(),
+    ///     "error  [E0001]: first\nwarning[W0001]: second\n",
+    /// );
+    ///
+    /// let builder = builder.with_severity_prefix(SeverityPrefix::Symbol);
+    /// assert_eq!(
+    ///     builder.as_string(&report).unwrap(),
+    ///     "[E][E0001]: first\n[W][W0001]: second\n",
+    /// );
+    ///
+    /// let builder = builder.with_style(DisplayStyle::Compact);
+    /// assert_eq!(
+    ///     builder.as_string(&report).unwrap(),
+    ///     "[E][E0001]: first\n[W][W0001]: second\n",
+    /// );
+    ///
+    /// let builder = builder.with_style(DisplayStyle::Default);
+    /// assert!(builder.as_string(&report).unwrap().starts_with("error[E0001]: first"));
+    /// assert_eq!(report.issues[1].to_string(), "warning[W0001]: second");
+    /// ```
+    #[must_use]
+    pub fn with_severity_prefix(mut self, prefix: SeverityPrefix) -> Self {
+        self.severity_prefix = prefix;
+
+        self
+    }
+
+    /// Set whether issues with a source should be rendered with a single primary label.
+    ///
+    /// This decides how the source of an issue is reconciled with its primary annotations:
+    ///
+    /// - when disabled, which is the default, explicit primary annotations drive the primary
+    ///   labels, and the source of an issue is only rendered when it has no primary
+    ///   annotation, so that an issue can point at several primary spans.
+    /// - when enabled, the source of an issue is its single primary label, its primary
+    ///   annotations are rendered as secondary annotations, except for a primary annotation
+    ///   covering the same span as the source, which is merged with it.
+    ///
+    /// Either way, a source and a primary annotation covering the same span are rendered
+    /// as a single label.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// # use ara_source::source::Source;
+    /// # use ara_source::source::SourceKind;
+    /// # use ara_source::SourceMap;
+    /// # use ara_reporting::annotation::Annotation;
+    /// # use ara_reporting::builder::ColorChoice;
+    /// # use ara_reporting::builder::ReportBuilder;
+    /// # use ara_reporting::issue::Issue;
+    /// # use ara_reporting::Report;
+    /// let source = SourceMap::new(vec![
+    ///     Source::new(SourceKind::Script, "main.ara", "$a = 1 + 'x';"),
+    /// ]);
+    ///
+    /// let report = Report::new().with_issue(
+    ///     Issue::error("E0002", "match arms have incompatible types")
+    ///         .with_source("main.ara", 0, 13)
+    ///         .with_annotation(Annotation::primary("main.ara", 5, 6).with_message("this is `int`"))
+    ///         .with_annotation(Annotation::primary("main.ara", 9, 12).with_message("this is `string`")),
+    /// );
+    ///
+    /// let builder = ReportBuilder::new(&source).with_colors(ColorChoice::Never);
+    /// assert!(!builder.single_primary);
+    ///
+    /// let output = builder.as_string(&report).unwrap();
+    /// assert_eq!(output, [
+    ///     "error[E0002]: match arms have incompatible types",
+    ///     "  --> main.ara:1:6",
+    ///     "  |",
+    ///     "1 | $a = 1 + 'x';",
+    ///     "  |      ^   ^^^ this is `string`",
+    ///     "  |      |    ",
+    ///     "  |      this is `int`",
+    ///     "",
+    ///     "",
+    /// ].join("\n"));
+    ///
+    /// let report = Report::new().with_issue(
+    ///     Issue::error("E0001", "mismatched types")
+    ///         .with_source("main.ara", 9, 12)
+    ///         .with_annotation(Annotation::primary("main.ara", 9, 12).with_message("this is `string`"))
+    ///         .with_annotation(Annotation::primary("main.ara", 5, 6).with_message("this is `int`")),
+    /// );
+    ///
+    /// let output = builder.clone().as_string(&report).unwrap();
+    /// assert!(output.contains("  |      ^   ^^^ this is `string`\n"));
+    /// assert!(output.contains("  |      this is `int`\n"));
+    ///
+    /// let output = builder.with_single_primary(true).as_string(&report).unwrap();
+    /// assert!(output.contains("  |      -   ^^^ this is `string`\n"));
+    /// ```
+    #[must_use]
+    pub fn with_single_primary(mut self, enabled: bool) -> Self {
+        self.single_primary = enabled;
+
+        self
+    }
+
+    /// Set whether a legend of the primary and secondary carets should be rendered once,
+    /// after the first issue rendered with both.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// # use ara_source::source::Source;
+    /// # use ara_source::source::SourceKind;
+    /// # use ara_source::SourceMap;
+    /// # use ara_reporting::annotation::Annotation;
+    /// # use ara_reporting::builder::ColorChoice;
+    /// # use ara_reporting::builder::ReportBuilder;
+    /// # use ara_reporting::issue::Issue;
+    /// # use ara_reporting::Report;
+    /// let source = SourceMap::new(vec![
+    ///     Source::new(SourceKind::Script, "main.ara", "$a = 1 + 'x';"),
+    /// ]);
+    ///
+    /// let mismatch = Issue::error("E0001", "mismatched types")
+    ///     .with_source("main.ara", 9, 12)
+    ///     .with_annotation(Annotation::secondary("main.ara", 5, 6).with_message("this is `int`"));
+    ///
+    /// let builder = ReportBuilder::new(&source)
+    ///     .with_colors(ColorChoice::Never)
+    ///     .with_caret_legend(true);
+    /// assert!(builder.caret_legend);
+    ///
+    /// let report = Report::new()
+    ///     .with_issue(Issue::warning("W0001", "unused variable").with_source("main.ara", 0, 2))
+    ///     .with_issue(mismatch.clone())
+    ///     .with_issue(mismatch);
+    ///
+    /// let output = builder.as_string(&report).unwrap();
+    /// let legend = "^ = primary location, - = related location\n";
+    /// assert_eq!(output.matches(legend).count(), 1);
+    /// assert!(output.find(legend).unwrap() > output.find("this is `int`").unwrap());
+    ///
+    /// let report = Report::new()
+    ///     .with_issue(Issue::warning("W0001", "unused variable").with_source("main.ara", 0, 2));
+    /// assert!(!builder.as_string(&report).unwrap().contains(legend));
+    /// ```
+    #[must_use]
+    pub fn with_caret_legend(mut self, enabled: bool) -> Self {
+        self.caret_legend = enabled;
+
+        self
+    }
+
+    /// Set the codespan config to render with.
+    ///
+    /// This is an escape hatch for codespan options the builder does not expose, the given
+    /// config replaces the one built from the display style and charset. Its styles and
+    /// chars are replaced by the builder ones, unless disabled with
+    /// `ReportBuilder::with_config_styles`.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// # use ara_source::source::Source;
+    /// # use ara_source::source::SourceKind;
+    /// # use ara_source::SourceMap;
+    /// # use ara_reporting::builder::ColorChoice;
+    /// # use ara_reporting::builder::ReportBuilder;
+    /// # use ara_reporting::issue::Issue;
+    /// # use ara_reporting::Report;
+    /// use codespan_reporting::term::Config;
+    ///
+    /// let source = SourceMap::new(vec![
+    ///     Source::new(SourceKind::Script, "main.ara", "a\nb\nc\nd\ne\nf"),
+    /// ]);
+    ///
+    /// let report = Report::new()
+    ///     .with_issue(Issue::error("E0001", "...").with_source("main.ara", 0, 11));
+    ///
+    /// let output = ReportBuilder::new(&source)
+    ///     .with_colors(ColorChoice::Never)
+    ///     .with_config(Config {
+    ///         start_context_lines: 0,
+    ///         end_context_lines: 0,
+    ///         ..Config::default()
+    ///     })
+    ///     .as_string(&report)
+    ///     .unwrap();
+    ///
+    /// assert!(output.contains("1 | / a\n  .  \n6 | | f\n"));
+    /// assert!(!output.contains("2 | | b"));
+    /// ```
+    #[must_use]
+    pub fn with_config(mut self, config: Config) -> Self {
+        self.config = Some(config);
+
+        self
+    }
+
+    /// Set whether the builder styles and chars replace the ones of the config set with
+    /// `ReportBuilder::with_config`, enabled by default.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// # use ara_source::source::Source;
+    /// # use ara_source::source::SourceKind;
+    /// # use ara_source::SourceMap;
+    /// # use ara_reporting::builder::ColorChoice;
+    /// # use ara_reporting::builder::ReportBuilder;
+    /// # use ara_reporting::issue::Issue;
+    /// # use ara_reporting::Report;
+    /// use codespan_reporting::term::Chars;
+    /// use codespan_reporting::term::Config;
+    ///
+    /// let source = SourceMap::new(vec![
+    ///     Source::new(SourceKind::Script, "main.ara", "function main(): void {}"),
+    /// ]);
+    ///
+    /// let report = Report::new()
+    ///     .with_issue(Issue::error("E0001", "...").with_source("main.ara", 9, 13));
+    ///
+    /// let builder = ReportBuilder::new(&source)
+    ///     .with_colors(ColorChoice::Never)
+    ///     .with_config(Config {
+    ///         chars: Chars::box_drawing(),
+    ///         ..Config::default()
+    ///     });
+    ///
+    /// assert!(builder.config_styles);
+    /// assert!(builder.as_string(&report).unwrap().contains("--> main.ara:1:10"));
+    ///
+    /// let builder = builder.with_config_styles(false);
+    /// assert!(builder.as_string(&report).unwrap().contains("┌─ main.ara:1:10"));
+    /// ```
+    #[must_use]
+    pub fn with_config_styles(mut self, enabled: bool) -> Self {
+        self.config_styles = enabled;
+
+        self
+    }
+
+    /// Set whether `as_string` should trim trailing whitespace from each line, and collapse
+    /// trailing newlines into one.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// # use ara_source::source::Source;
+    /// # use ara_source::source::SourceKind;
+    /// # use ara_source::SourceMap;
+    /// # use ara_reporting::builder::ColorChoice;
+    /// # use ara_reporting::builder::ReportBuilder;
+    /// # use ara_reporting::issue::Issue;
+    /// # use ara_reporting::Report;
+    /// let source = SourceMap::new(vec![
+    ///     Source::new(SourceKind::Script, "main.ara", "function main(): void {\n}"),
+    /// ]);
+    ///
+    /// let report = Report::new()
+    ///     .with_issue(Issue::error("E0001", "...").with_source("main.ara", 0, 25));
+    ///
+    /// let builder = ReportBuilder::new(&source).with_colors(ColorChoice::Never);
+    /// let output = builder.as_string(&report).unwrap();
+    /// assert!(output.contains("  |  \n"));
+    /// assert!(output.ends_with("\n\n"));
+    ///
+    /// let output = builder.with_trim_trailing_whitespace(true).as_string(&report).unwrap();
+    /// assert_eq!(output, [
+    ///     "error[E0001]: ...",
+    ///     "  --> main.ara:1:1",
+    ///     "  |",
+    ///     "1 | / function main(): void {",
+    ///     "2 | | }",
+    ///     "  | \\-^",
+    ///     "",
+    /// ].join("\n"));
+    /// ```
+    #[must_use]
+    pub fn with_trim_trailing_whitespace(mut self, enabled: bool) -> Self {
+        self.trim_trailing_whitespace = enabled;
+
+        self
+    }
+
+    /// Set the number of columns a tab character is rendered with, in code frames.
+    ///
+    /// Defaults to 2.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// # use ara_source::source::Source;
+    /// # use ara_source::source::SourceKind;
+    /// # use ara_source::SourceMap;
+    /// # use ara_reporting::builder::ColorChoice;
+    /// # use ara_reporting::builder::ReportBuilder;
+    /// # use ara_reporting::issue::Issue;
+    /// # use ara_reporting::Report;
+    /// let source = SourceMap::new(vec![
+    ///     Source::new(SourceKind::Script, "main.ara", "\t$a = 1;"),
+    /// ]);
+    ///
+    /// let report = Report::new().with_issue(Issue::error("E0001", "...").with_source("main.ara", 1, 3));
+    ///
+    /// let builder = ReportBuilder::new(&source).with_colors(ColorChoice::Never);
+    /// assert_eq!(builder.tab_width, 2);
+    ///
+    /// let output = builder.clone().with_tab_width(4).as_string(&report).unwrap();
+    /// assert!(output.contains("1 |     $a = 1;\n  |     ^^\n"));
+    ///
+    /// let output = builder.with_tab_width(8).as_string(&report).unwrap();
+    /// assert!(output.contains("1 |         $a = 1;\n  |         ^^\n"));
+    /// ```
+    #[must_use]
+    pub fn with_tab_width(mut self, width: usize) -> Self {
+        self.tab_width = width;
+
+        self
+    }
+
+    /// Set the number of lines rendered after the start of a multi-line span.
+    ///
+    /// Defaults to 1.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// # use ara_source::source::Source;
+    /// # use ara_source::source::SourceKind;
+    /// # use ara_source::SourceMap;
+    /// # use ara_reporting::builder::ColorChoice;
+    /// # use ara_reporting::builder::ReportBuilder;
+    /// # use ara_reporting::issue::Issue;
+    /// # use ara_reporting::Report;
+    /// let source = SourceMap::new(vec![
+    ///     Source::new(SourceKind::Script, "main.ara", "a\nb\nc\nd\ne\nf\ng\nh\ni\nj\n"),
+    /// ]);
+    ///
+    /// let report = Report::new().with_issue(Issue::error("E0001", "...").with_source("main.ara", 0, 19));
+    ///
+    /// let builder = ReportBuilder::new(&source).with_colors(ColorChoice::Never);
+    /// assert_eq!(builder.start_context_lines, 1);
+    ///
+    /// let output = builder.clone().as_string(&report).unwrap();
+    /// assert!(output.contains(" 2 | | b\n   . |\n"));
+    ///
+    /// let output = builder.with_start_context_lines(3).as_string(&report).unwrap();
+    /// assert!(output.contains(" 2 | | b\n 3 | | c\n 4 | | d\n   . |\n"));
+    /// ```
+    #[must_use]
+    pub fn with_start_context_lines(mut self, lines: usize) -> Self {
+        self.start_context_lines = lines;
+
+        self
+    }
+
+    /// Set the number of lines rendered before the end of a multi-line span.
+    ///
+    /// Defaults to 1.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// # use ara_source::source::Source;
+    /// # use ara_source::source::SourceKind;
+    /// # use ara_source::SourceMap;
+    /// # use ara_reporting::builder::ColorChoice;
+    /// # use ara_reporting::builder::ReportBuilder;
+    /// # use ara_reporting::issue::Issue;
+    /// # use ara_reporting::Report;
+    /// let source = SourceMap::new(vec![
+    ///     Source::new(SourceKind::Script, "main.ara", "a\nb\nc\nd\ne\nf\ng\nh\ni\nj\n"),
+    /// ]);
+    ///
+    /// let report = Report::new().with_issue(Issue::error("E0001", "...").with_source("main.ara", 0, 19));
+    ///
+    /// let builder = ReportBuilder::new(&source).with_colors(ColorChoice::Never);
+    /// assert_eq!(builder.end_context_lines, 1);
+    ///
+    /// let output = builder.clone().as_string(&report).unwrap();
+    /// assert!(output.contains("   . |\n 9 | | i\n10 | | j\n"));
+    ///
+    /// let output = builder.with_end_context_lines(3).as_string(&report).unwrap();
+    /// assert!(output.contains("   . |\n 7 | | g\n 8 | | h\n 9 | | i\n10 | | j\n"));
+    /// ```
+    #[must_use]
+    pub fn with_end_context_lines(mut self, lines: usize) -> Self {
+        self.end_context_lines = lines;
+
+        self
+    }
+
+    /// Print the report to stdout.
+    pub fn print(&self, reportable: &dyn Reportable) -> Result<(), Error> {
+        let mut writer =
+            StandardStream::stdout(self.resolve_color_choice(std::io::stdout().is_terminal()));
+
+        self.write(&mut writer, reportable)
+    }
+
+    /// Print the report to stderr.
+    pub fn eprint(&self, reportable: &dyn Reportable) -> Result<(), Error> {
+        let mut writer =
+            StandardStream::stderr(self.resolve_color_choice(std::io::stderr().is_terminal()));
+
+        self.write(&mut writer, reportable)
+    }
+
+    /// Get the report as a string.
+    pub fn as_string(&self, reportable: &dyn Reportable) -> Result<String, Error> {
+        let buffer =
+            BufferWriter::stderr(self.resolve_color_choice(std::io::stderr().is_terminal()));
+
+        let mut buffer = buffer.buffer();
+
+        self.write(&mut buffer, reportable)?;
+
+        let output = String::from_utf8_lossy(buffer.as_slice()).to_string();
+        if !self.trim_trailing_whitespace {
+            return Ok(output);
+        }
+
+        let output = output
+            .split('\n')
+            .map(|line| line.trim_end_matches([' ', '\t']))
+            .collect::<Vec<&str>>()
+            .join("\n");
+        let output = output.trim_end_matches('\n');
+
+        Ok(if output.is_empty() {
+            String::new()
+        } else {
+            format!("{output}\n")
+        })
+    }
+
+    /// Render the report, without colors, into the given `fmt::Write` target.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// # use ara_source::source::Source;
+    /// # use ara_source::source::SourceKind;
+    /// # use ara_source::SourceMap;
+    /// # use ara_reporting::builder::ReportBuilder;
+    /// # use ara_reporting::builder::ColorChoice;
+    /// # use ara_reporting::issue::Issue;
+    /// # use ara_reporting::Report;
+    /// # let source = SourceMap::new(vec![
+    /// #     Source::new(SourceKind::Script, "main.ara", "function main(): void {}"),
+    /// # ]);
+    /// let report = Report::new().with_issue(Issue::error("E0001", "something went wrong"));
+    ///
+    /// let mut output = String::from("## Diagnostics\n\n");
+    /// ReportBuilder::new(&source)
+    ///     .with_colors(ColorChoice::Always)
+    ///     .render_to_fmt(&mut output, &report)
+    ///     .unwrap();
+    ///
+    /// assert!(output.starts_with("## Diagnostics\n\nerror[E0001]: something went wrong"));
+    /// assert!(!output.contains('\u{1b}'));
+    /// ```
+    pub fn render_to_fmt<W: std::fmt::Write>(
+        &self,
+        w: &mut W,
+        reportable: &dyn Reportable,
+    ) -> Result<(), Error> {
+        let mut buffer = NoColor::new(Vec::new());
+
+        self.write(&mut buffer, reportable)?;
+
+        w.write_str(&String::from_utf8_lossy(&buffer.into_inner()))
+            .map_err(Error::Fmt)
+    }
+
+    /// Render the report as styled lines, for consumers laying out the output themselves.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// # use ara_source::SourceMap;
+    /// # use ara_reporting::builder::ReportBuilder;
+    /// # use ara_reporting::issue::Issue;
+    /// # use ara_reporting::Report;
+    /// # use ara_reporting::styled::Role;
+    /// # use termcolor::Color;
+    /// # let source = SourceMap::new(vec![]);
+    /// let report = Report::new()
+    ///     .with_issue(Issue::error("E0001", "something went wrong").with_note("this is a note"));
+    ///
+    /// let lines = ReportBuilder::new(&source).to_render_lines(&report).unwrap();
+    ///
+    /// let (text, style) = &lines[0][0];
+    /// assert_eq!(text, "error[E0001]");
+    /// assert_eq!(style.foreground, Some(Color::Red));
+    /// assert!(style.bold);
+    /// assert_eq!(style.role, Some(Role::HeaderError));
+    ///
+    /// let (text, style) = &lines[0][1];
+    /// assert_eq!(text, ": something went wrong");
+    /// assert_eq!(style.foreground, None);
+    ///
+    /// let line = lines[1].iter().map(|(text, _)| text.as_str()).collect::<String>();
+    /// assert_eq!(line, " = this is a note");
+    /// ```
+    pub fn to_render_lines(&self, reportable: &dyn Reportable) -> Result<Vec<StyledLine>, Error> {
+        let config = self.config();
+        let (files, files_ids) = self.files();
+
+        let mut writer = StyledLinesWriter::new(&config.styles);
+        self.write_with_config(&mut writer, &config, reportable, &files, &files_ids)?;
+
+        Ok(writer.into_lines())
+    }
+
+    /// Render the report as lines of segments, each carrying the CSS class of the element
+    /// it renders, for web frontends applying their own stylesheet.
+    ///
+    /// Classes are those of the roles of the styled lines, see `Role::class`.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// # use ara_source::source::Source;
+    /// # use ara_source::source::SourceKind;
+    /// # use ara_source::SourceMap;
+    /// # use ara_reporting::builder::ReportBuilder;
+    /// # use ara_reporting::issue::Issue;
+    /// # use ara_reporting::Report;
+    /// use ara_reporting::styled::HtmlSegment;
+    ///
+    /// let source = SourceMap::new(vec![
+    ///     Source::new(SourceKind::Script, "main.ara", "function main(): void {}"),
+    /// ]);
+    ///
+    /// let report = Report::new()
+    ///     .with_issue(Issue::error("E0001", "something went wrong").with_source("main.ara", 9, 13));
+    ///
+    /// let lines = ReportBuilder::new(&source).to_html_segments(&report).unwrap();
+    ///
+    /// assert_eq!(lines[0], vec![
+    ///     HtmlSegment { text: "error[E0001]".to_string(), class: Some("severity-error") },
+    ///     HtmlSegment { text: ": something went wrong".to_string(), class: Some("header-message") },
+    /// ]);
+    /// assert_eq!(lines[3][0], HtmlSegment { text: "1".to_string(), class: Some("line-number") });
+    /// assert!(lines[4].contains(&HtmlSegment {
+    ///     text: "^^^^".to_string(),
+    ///     class: Some("annotation-primary"),
+    /// }));
+    /// ```
+    pub fn to_html_segments(&self, reportable: &dyn Reportable) -> Result<Vec<HtmlLine>, Error> {
+        let lines = self.to_render_lines(reportable)?;
+
+        Ok(lines.iter().map(styled::html_line).collect())
+    }
+
+    /// Write the report to the given writer.
+    pub fn write<T: WriteColor>(&self, w: T, reportable: &dyn Reportable) -> Result<(), Error> {
+        let (files, files_ids) = self.files();
+
+        self.write_with_config(w, &self.config(), reportable, &files, &files_ids)
+    }
+
+    /// Write the report to the given writer, using the given files database.
+    ///
+    /// This skips building a files database from the source map, label file ids are looked
+    /// up by origin in `name_to_id`. The content of the origins referenced by the report is
+    /// read from `files` as well, in place of the source map, so that features relying on
+    /// it (e.g. the inline style, encoding checks, and annotation groups) render the same
+    /// as with `ReportBuilder::write`.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// # use std::collections::HashMap;
+    /// # use ara_source::SourceMap;
+    /// # use ara_reporting::builder::ReportBuilder;
+    /// # use ara_reporting::builder::DisplayStyle;
+    /// # use ara_reporting::issue::Issue;
+    /// # use ara_reporting::Report;
+    /// use codespan_reporting::files::SimpleFiles;
+    /// use termcolor::NoColor;
+    ///
+    /// let mut files = SimpleFiles::new();
+    /// let mut name_to_id = HashMap::new();
+    /// name_to_id.insert("main.ara".to_string(), files.add("main.ara", "function main(): void {}"));
+    ///
+    /// let report = Report::new()
+    ///     .with_issue(Issue::error("E0001", "...").with_source("main.ara", 9, 13));
+    ///
+    /// let source_map = SourceMap::new(vec![]);
+    /// let mut writer = NoColor::new(vec![]);
+    /// ReportBuilder::new(&source_map)
+    ///     .write_with_files(&mut writer, &report, &files, &name_to_id)
+    ///     .unwrap();
+    ///
+    /// let output = String::from_utf8(writer.into_inner()).unwrap();
+    /// assert!(output.contains("--> main.ara:1:10"));
+    /// assert!(output.contains("function main(): void {}"));
+    ///
+    /// let mut writer = NoColor::new(vec![]);
+    /// ReportBuilder::new(&source_map)
+    ///     .with_style(DisplayStyle::Inline)
+    ///     .write_with_files(&mut writer, &report, &files, &name_to_id)
+    ///     .unwrap();
+    ///
+    /// let output = String::from_utf8(writer.into_inner()).unwrap();
+    /// assert_eq!(output, "main.ara:1:10 error[E0001]: ...\nfunction main(): void {}\n         ^^^^\n");
+    /// ```
+    pub fn write_with_files<'f, T: WriteColor, F: Files<'f, FileId = usize>, S: BuildHasher>(
+        &self,
+        w: T,
+        reportable: &dyn Reportable,
+        files: &'f F,
+        name_to_id: &HashMap<String, usize, S>,
+    ) -> Result<(), Error> {
+        let mut sources: Vec<Source> = vec![];
+        for report in reportable.to_reports() {
+            let origins = report
+                .issues
+                .iter()
+                .flat_map(|issue| {
+                    issue.source.iter().map(|(origin, _, _)| origin).chain(
+                        issue
+                            .annotations
+                            .iter()
+                            .map(|annotation| &annotation.origin),
+                    )
+                })
+                .chain(report.default_origin.iter());
+
+            for origin in origins {
+                if sources.iter().any(|source| source.name() == origin) {
+                    continue;
+                }
+
+                if let Some(content) = name_to_id.get(origin).and_then(|id| files.source(*id).ok())
+                {
+                    sources.push(Source::new(
+                        SourceKind::Script,
+                        origin.as_str(),
+                        content.as_ref(),
+                    ));
+                }
+            }
+        }
+
+        let source_map = SourceMap::new(sources);
+        let builder = ReportBuilder {
+            source_map: &source_map,
+            ..self.clone()
+        };
+
+        builder.write_with_config(w, &builder.config(), reportable, files, name_to_id)
+    }
+
+    /// Write the report to the given writer, rendered with the given config.
+    fn write_with_config<'f, T: WriteColor, F: Files<'f, FileId = usize>, S: BuildHasher>(
+        &self,
+        mut w: T,
+        config: &Config,
+        reportable: &dyn Reportable,
+        files: &'f F,
+        name_to_id: &HashMap<String, usize, S>,
+    ) -> Result<(), Error> {
+        let mut output = CappedOutput::new(&mut w, &config.styles, self.max_output_bytes);
+        let mut emitted = 0;
+        let mut legend = self.caret_legend;
+        for report in reportable.to_reports() {
+            let (issues, omitted) = self.visible_issues(report);
+            let issues = issues
+                .into_iter()
+                .map(|issue| resolve_origins(issue, report.default_origin.as_deref()))
+                .collect::<Result<Vec<Cow<Issue>>, Error>>()?;
+
+            if self.encoding_policy == EncodingPolicy::Strict {
+                for issue in &issues {
+                    self.check_encoding(issue)?;
+                }
+            }
+
+            let mut groups: Vec<(Option<&str>, Vec<&Issue>)> = vec![];
+            for issue in &issues {
+                let origin = if self.file_banners {
+                    issue.origin()
+                } else {
+                    None
+                };
+
+                match groups.iter_mut().find(|(group, _)| *group == origin) {
+                    Some((_, group)) => group.push(issue),
+                    None => groups.push((origin, vec![issue])),
+                }
+            }
+
+            for (origin, issues) in groups {
+                if let Some(origin) = origin {
+                    output.chunk(|mut w| {
+                        self.write_file_banner(&mut w, config, origin, issues.len())
+                    })?;
+                }
+
+                for issue in issues {
+                    output.chunk(|mut w| {
+                        if emitted > 0 {
+                            self.write_separator(&mut w)?;
+                        }
+
+                        self.write_issue(&mut w, config, files, name_to_id, issue)?;
+
+                        if legend && self.mixes_label_styles(issue, name_to_id) {
+                            legend = false;
+
+                            let chars = &config.chars;
+                            writeln!(
+                                w,
+                                "{} = primary location, {} = related location\n",
+                                chars.single_primary_caret, chars.single_secondary_caret
+                            )
+                            .map_err(Error::Io)?;
+                        }
+
+                        Ok(())
+                    })?;
+                    emitted += 1;
+                }
+            }
+
+            if omitted > 0 {
+                output.chunk(|w| {
+                    writeln!(w, "… {omitted} lower-severity issues hidden").map_err(Error::Io)
+                })?;
+            }
+
+            if let Some(footer) = &report.footer {
+                let counts = self.summary_counts(&report.issues);
+                output.chunk(|mut w| self.write_footer(&mut w, config, files, &counts, footer))?;
+            }
+        }
+
+        if let Some((counts, footer)) = self.trailing_counts(reportable) {
+            output.chunk(|mut w| self.write_footer(&mut w, config, files, &counts, footer))?;
+        }
+
+        output.finish()
+    }
+
+    /// Write the report as newline-delimited JSON.
+    ///
+    /// Each rendered issue is written as a single JSON object, with a `"type": "issue"`
+    /// discriminator and its resolved `origin`, `line`, and `column`. The footer of each
+    /// report is written as a final object with a `"type": "summary"` discriminator, its
+    /// `counts` and `hidden` count match the summary line of the footer. Every object carries
+    /// the `schema_version` of its shape, see `REPORT_SCHEMA_VERSION`.
+    ///
+    /// The writer is flushed after each line, so that a consumer tailing the output
+    /// sees issues as they are written.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// use ara_source::source::Source;
+    /// use ara_source::source::SourceKind;
+    /// use ara_source::SourceMap;
+    ///
+    /// use ara_reporting::builder::ReportBuilder;
+    /// use ara_reporting::issue::Issue;
+    /// use ara_reporting::Report;
+    /// use ara_reporting::ReportFooter;
+    ///
+    /// let source = SourceMap::new(vec![
+    ///     Source::new(SourceKind::Script, "main.ara", "function main(): void {\n  $a = 1;\n}"),
+    /// ]);
+    ///
+    /// let report = Report::new()
+    ///     .with_issue(Issue::warning("W0001", "unused variable `$a`").with_source("main.ara", 26, 28))
+    ///     .with_footer(ReportFooter::new("done"));
+    ///
+    /// let mut output = vec![];
+    /// ReportBuilder::new(&source).write_ndjson(&mut output, &report).unwrap();
+    ///
+    /// let output = String::from_utf8(output).unwrap();
+    /// let lines = output
+    ///     .lines()
+    ///     .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap())
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(lines.len(), 2);
+    /// assert_eq!(lines[0]["type"], "issue");
+    /// assert_eq!(lines[0]["schema_version"], ara_reporting::REPORT_SCHEMA_VERSION);
+    /// assert_eq!(lines[0]["code"], "W0001");
+    /// assert_eq!(lines[0]["origin"], "main.ara");
+    /// assert_eq!(lines[0]["line"], 2);
+    /// assert_eq!(lines[0]["column"], 3);
+    /// assert_eq!(lines[1]["type"], "summary");
+    /// assert_eq!(lines[1]["schema_version"], ara_reporting::REPORT_SCHEMA_VERSION);
+    /// assert_eq!(lines[1]["message"], "done");
+    /// assert_eq!(lines[1]["counts"]["warning"], 1);
+    /// ```
+    pub fn write_ndjson<W: Write>(
+        &self,
+        mut w: W,
+        reportable: &dyn Reportable,
+    ) -> Result<(), Error> {
+        let mut write_line = |mut value: serde_json::Value| -> Result<(), Error> {
+            if let serde_json::Value::Object(object) = &mut value {
+                object.insert("schema_version".to_string(), REPORT_SCHEMA_VERSION.into());
+            }
+
+            serde_json::to_writer(&mut w, &value).map_err(json_error)?;
+            writeln!(w).map_err(Error::Io)?;
+
+            w.flush().map_err(Error::Io)
+        };
+
+        for report in reportable.to_reports() {
+            let (issues, _) = self.visible_issues(report);
+            for issue in issues {
+                let issue = resolve_origins(issue, report.default_origin.as_deref())?;
+
+                let mut shifted = issue.clone().into_owned();
+                if let Some((origin, from, to)) = &mut shifted.source {
+                    let (_, byte_offset) = self.source_offset(origin);
+                    *from += byte_offset;
+                    *to += byte_offset;
+                }
+                for annotation in &mut shifted.annotations {
+                    let (_, byte_offset) = self.source_offset(&annotation.origin);
+                    annotation.from += byte_offset;
+                    annotation.to += byte_offset;
+                    for (from, to) in &mut annotation.ranges {
+                        *from += byte_offset;
+                        *to += byte_offset;
+                    }
+                }
+
+                let mut value = serde_json::to_value(&shifted).map_err(Error::Serialization)?;
+                if let serde_json::Value::Object(object) = &mut value {
+                    let span = primary_span(&issue);
+                    let location = span.and_then(|(origin, from, _)| self.locate(origin, from));
+
+                    object.insert("type".to_string(), "issue".into());
+                    object.insert(
+                        "origin".to_string(),
+                        span.map(|(origin, _, _)| origin).into(),
+                    );
+                    object.insert("line".to_string(), location.map(|(line, _)| line).into());
+                    object.insert(
+                        "column".to_string(),
+                        location.map(|(_, column)| column).into(),
+                    );
+                }
+
+                write_line(value)?;
+            }
+
+            if let Some(footer) = &report.footer {
+                let counts = self.summary_counts(&report.issues);
+                if is_footer_shown(&counts, footer) {
+                    write_line(self.summary_value(&counts, footer))?;
+                }
+            }
+        }
+
+        if let Some((counts, footer)) = self.trailing_counts(reportable) {
+            if is_footer_shown(&counts, footer) {
+                write_line(self.summary_value(&counts, footer))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Get the report as a GitLab code quality report.
+    ///
+    /// Severities are mapped from note, help, warning, error and bug to `info`, `minor`,
+    /// `major`, `critical` and `blocker`, and issues are identified by their fingerprint.
+    /// The `check_name` is the issue code, or its severity for issues without a code.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// use ara_source::source::Source;
+    /// use ara_source::source::SourceKind;
+    /// use ara_source::SourceMap;
+    ///
+    /// use ara_reporting::builder::ReportBuilder;
+    /// use ara_reporting::issue::Issue;
+    /// use ara_reporting::issue::IssueSeverity;
+    /// use ara_reporting::Report;
+    ///
+    /// let source = SourceMap::new(vec![
+    ///     Source::new(SourceKind::Script, "main.ara", "function main(): void {\n  $a = 1;\n}"),
+    /// ]);
+    ///
+    /// let issue = Issue::warning("W0001", "unused variable `$a`").with_source("main.ara", 26, 28);
+    /// let report = Report::new()
+    ///     .with_issue(issue.clone())
+    ///     .with_issue(Issue::new(IssueSeverity::Error, "failed to parse `main.ara`"));
+    ///
+    /// let json = ReportBuilder::new(&source).as_code_quality(&report).unwrap();
+    /// let json: serde_json::Value = serde_json::from_str(&json).unwrap();
+    ///
+    /// assert_eq!(json[0]["description"], "unused variable `$a`");
+    /// assert_eq!(json[0]["check_name"], "W0001");
+    /// assert_eq!(json[0]["fingerprint"], format!("{:016x}", issue.fingerprint()));
+    /// assert_eq!(json[0]["severity"], "major");
+    /// assert_eq!(json[0]["location"]["path"], "main.ara");
+    /// assert_eq!(json[0]["location"]["lines"]["begin"], 2);
+    ///
+    /// assert_eq!(json[1]["check_name"], "error");
+    /// ```
+    pub fn as_code_quality(&self, reportable: &dyn Reportable) -> Result<String, Error> {
+        let mut entries = vec![];
+        for report in reportable.to_reports() {
+            let (issues, _) = self.visible_issues(report);
+            for issue in issues {
+                let issue = resolve_origins(issue, report.default_origin.as_deref())?;
+
+                let span = primary_span(&issue);
+                let begin = span
+                    .and_then(|(origin, from, _)| self.locate(origin, from))
+                    .map_or(1, |(line, _)| line);
+
+                entries.push(serde_json::json!({
+                    "description": issue.message,
+                    "check_name": issue
+                        .code
+                        .clone()
+                        .unwrap_or_else(|| issue.severity.to_string()),
+                    "fingerprint": format!("{:016x}", issue.fingerprint()),
+                    "severity": match issue.severity {
+                        IssueSeverity::Note => "info",
+                        IssueSeverity::Help => "minor",
+                        IssueSeverity::Warning => "major",
+                        IssueSeverity::Error => "critical",
+                        IssueSeverity::Bug => "blocker",
+                    },
+                    "location": {
+                        "path": span.map_or(DEFAULT_NAME, |(origin, _, _)| origin),
+                        "lines": {
+                            "begin": begin,
+                        },
+                    },
+                }));
+            }
+        }
+
+        serde_json::to_string(&entries).map_err(Error::Serialization)
+    }
+
+    /// Get the report as a JSON array of reports, holding their visible issues.
+    ///
+    /// Unlike `OutputFormat::Json`, the array is not wrapped in a versioned envelope.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// use ara_source::SourceMap;
+    ///
+    /// use ara_reporting::annotation::Annotation;
+    /// use ara_reporting::builder::ReportBuilder;
+    /// use ara_reporting::issue::Issue;
+    /// use ara_reporting::Report;
+    /// use ara_reporting::ReportFooter;
+    ///
+    /// let report = Report::new()
+    ///     .with_issue(
+    ///         Issue::error("E0001", "...")
+    ///             .with_source("main.ara", 9, 13)
+    ///             .with_annotation(Annotation::secondary("main.ara", 0, 8)),
+    ///     )
+    ///     .with_footer(ReportFooter::new("done"));
+    ///
+    /// let source_map = SourceMap::new(vec![]);
+    /// let json = ReportBuilder::new(&source_map).as_json(&report).unwrap();
+    /// let json: serde_json::Value = serde_json::from_str(&json).unwrap();
+    ///
+    /// assert_eq!(json[0]["issues"][0]["source"], serde_json::json!(["main.ara", 9, 13]));
+    /// assert_eq!(json[0]["issues"][0]["annotations"][0]["from"], 0);
+    /// assert_eq!(json[0]["footer"]["message"], "done");
+    ///
+    /// assert_eq!(serde_json::from_value::<Vec<Report>>(json).unwrap(), vec![report]);
+    /// ```
+    pub fn as_json(&self, reportable: &dyn Reportable) -> Result<String, Error> {
+        serde_json::to_string(&self.visible_reports(reportable)?).map_err(Error::Serialization)
+    }
+
+    /// Get the report as a SARIF 2.1.0 log, with a single run holding a result per rendered
+    /// issue.
+    ///
+    /// Issues are filtered and ordered as when rendering. They map to results, with their
+    /// code as `ruleId`, and their severity as `level` (`error` for bugs and errors,
+    /// `warning` for warnings, and `note` for notes and help).
+    ///
+    /// The source and primary annotations of an issue map to its `locations`, and secondary
+    /// annotations to its `relatedLocations`. Byte offsets are converted to 1-based lines and
+    /// columns, counted in unicode code points, source offsets are applied to both, and
+    /// spans that can not be located in the source map have no `region`.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// use ara_source::source::Source;
+    /// use ara_source::source::SourceKind;
+    /// use ara_source::SourceMap;
+    ///
+    /// use ara_reporting::builder::ReportBuilder;
+    /// use ara_reporting::issue::Issue;
+    /// use ara_reporting::Report;
+    ///
+    /// let source = SourceMap::new(vec![
+    ///     Source::new(SourceKind::Script, "README.md", "function main(): void {\n  $a = 1;\n}"),
+    /// ]);
+    ///
+    /// let report = Report::new()
+    ///     .with_issue(Issue::warning("W0001", "unused variable `$a`").with_source("README.md", 26, 28))
+    ///     .with_issue(Issue::error("E0001", "...").with_source("README.md", 9, 13))
+    ///     .with_issue(Issue::note("N0001", "..."));
+    ///
+    /// let sarif = ReportBuilder::new(&source)
+    ///     .with_top(Some(2))
+    ///     .with_source_offset("README.md", 41, 1024)
+    ///     .as_sarif(&report)
+    ///     .unwrap();
+    /// let sarif: serde_json::Value = serde_json::from_str(&sarif).unwrap();
+    ///
+    /// let results = sarif["runs"][0]["results"].as_array().unwrap();
+    /// assert_eq!(results.len(), 2);
+    /// assert_eq!(results[0]["ruleId"], "E0001");
+    /// assert_eq!(results[1]["ruleId"], "W0001");
+    ///
+    /// let region = &results[1]["locations"][0]["physicalLocation"]["region"];
+    /// assert_eq!(region["startLine"], 43);
+    /// assert_eq!(region["startColumn"], 3);
+    /// assert_eq!(region["byteOffset"], 1050);
+    /// ```
+    pub fn as_sarif(&self, reportable: &dyn Reportable) -> Result<String, Error> {
+        sarif::sarif(self, reportable)
+    }
+
+    /// Get the report as GitHub Actions workflow commands, one per line and rendered issue,
+    /// displayed by GitHub as annotations.
+    ///
+    /// Issues are filtered and ordered as when rendering. Severities map to commands:
+    /// `error` for bugs and errors, `warning` for warnings, and `notice` for notes and help.
+    /// The code of an issue is its `title`.
+    ///
+    /// Each command points at the primary span of its issue, see `primary_span`, with its
+    /// 1-based lines and columns, source offsets applied. Spans that can not be located in
+    /// the source map only have a `file`. Commands can be wrapped in fold groups per origin,
+    /// see `ReportBuilder::with_fold_groups`.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// use ara_source::source::Source;
+    /// use ara_source::source::SourceKind;
+    /// use ara_source::SourceMap;
+    ///
+    /// use ara_reporting::builder::ReportBuilder;
+    /// use ara_reporting::issue::Issue;
+    /// use ara_reporting::issue::IssueSeverity;
+    /// use ara_reporting::Report;
+    ///
+    /// let source = SourceMap::new(vec![
+    ///     Source::new(SourceKind::Script, "README.md", "function main(): void {\n  $a = 1;\n}"),
+    /// ]);
+    ///
+    /// let report = Report::new()
+    ///     .with_issue(Issue::warning("W0001", "unused variable `$a`").with_source("README.md", 26, 28))
+    ///     .with_issue(Issue::note("N0001", "...").with_source("README.md", 9, 13));
+    ///
+    /// let commands = ReportBuilder::new(&source)
+    ///     .with_origin_min_severity("*.md", IssueSeverity::Warning)
+    ///     .with_source_offset("README.md", 41, 1024)
+    ///     .as_workflow_commands(&report)
+    ///     .unwrap();
+    ///
+    /// assert_eq!(
+    ///     commands,
+    ///     "::warning file=README.md,line=43,col=3,endLine=43,endColumn=5,title=W0001::unused variable `$a`\n",
+    /// );
+    /// ```
+    pub fn as_workflow_commands(&self, reportable: &dyn Reportable) -> Result<String, Error> {
+        github::workflow_commands(self, reportable)
+    }
+
+    /// Get the report as a checkstyle XML document.
+    ///
+    /// Issues are filtered and ordered as when rendering, grouped by the origin of their
+    /// primary span, see `primary_span`, into `<file>` elements, in the order their origins
+    /// first appear, and rendered as `<error>` elements. Issues without a span are grouped in
+    /// a `<file>` with an empty name.
+    ///
+    /// Severities map to checkstyle levels: `error` for bugs and errors, `warning` for
+    /// warnings, and `info` for notes and help. The code of an issue is its `source`.
+    /// Lines and columns are 1-based, source offsets applied, and omitted for spans that can
+    /// not be located in the source map.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// use ara_source::source::Source;
+    /// use ara_source::source::SourceKind;
+    /// use ara_source::SourceMap;
+    ///
+    /// use ara_reporting::builder::ReportBuilder;
+    /// use ara_reporting::issue::Issue;
+    /// use ara_reporting::Report;
+    ///
+    /// let source = SourceMap::new(vec![
+    ///     Source::new(SourceKind::Script, "README.md", "function main(): void {\n  $a = 1;\n}"),
+    /// ]);
+    ///
+    /// let report = Report::new()
+    ///     .with_issue(
+    ///         Issue::warning("W0001", "unused variable \x1b[1m`$a`\x1b[0m")
+    ///             .with_source("README.md", 26, 28),
+    ///     )
+    ///     .with_issue(Issue::note("N0001", "...").with_source("README.md", 9, 13).hidden());
+    ///
+    /// let document = ReportBuilder::new(&source)
+    ///     .with_source_offset("README.md", 41, 1024)
+    ///     .as_checkstyle(&report)
+    ///     .unwrap();
+    ///
+    /// assert!(document.contains(
+    ///     r#"<error line="43" column="3" severity="warning" message="unused variable [1m`$a`[0m" source="W0001"/>"#
+    /// ));
+    /// assert!(!document.contains("N0001"));
+    /// ```
+    pub fn as_checkstyle(&self, reportable: &dyn Reportable) -> Result<String, Error> {
+        checkstyle::checkstyle(self, reportable)
+    }
+
+    /// Write the report as `rustc --error-format=json` lines, one per issue.
+    ///
+    /// Issue notes are written as children of the issue, and the human rendering of each
+    /// issue is written to its `rendered` field.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// use ara_source::source::Source;
+    /// use ara_source::source::SourceKind;
+    /// use ara_source::SourceMap;
+    ///
+    /// use ara_reporting::annotation::Annotation;
+    /// use ara_reporting::builder::ReportBuilder;
+    /// use ara_reporting::issue::Issue;
+    /// use ara_reporting::Report;
+    ///
+    /// let source = SourceMap::new(vec![
+    ///     Source::new(SourceKind::Script, "main.ara", "function main(): void {\n  $a = 1;\n}"),
+    /// ]);
+    ///
+    /// let report = Report::new().with_issue(
+    ///     Issue::warning("W0001", "unused variable `$a`")
+    ///         .with_source("main.ara", 26, 28)
+    ///         .with_annotation(Annotation::secondary("main.ara", 9, 13).with_message("in this function"))
+    ///         .with_note("consider removing it"),
+    /// );
+    ///
+    /// let mut output = vec![];
+    /// ReportBuilder::new(&source).write_rustc_json(&mut output, &report).unwrap();
+    ///
+    /// let line: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    /// assert_eq!(line["message"], "unused variable `$a`");
+    /// assert_eq!(line["code"]["code"], "W0001");
+    /// assert_eq!(line["level"], "warning");
+    ///
+    /// let spans = line["spans"].as_array().unwrap();
+    /// assert_eq!(spans.len(), 2);
+    /// assert_eq!(spans[0]["label"], "in this function");
+    /// assert_eq!(spans[0]["is_primary"], false);
+    /// assert_eq!(spans[1]["file_name"], "main.ara");
+    /// assert_eq!(spans[1]["byte_start"], 26);
+    /// assert_eq!(spans[1]["line_start"], 2);
+    /// assert_eq!(spans[1]["column_start"], 3);
+    /// assert_eq!(spans[1]["column_end"], 5);
+    /// assert_eq!(spans[1]["is_primary"], true);
+    /// assert_eq!(spans[1]["text"][0]["text"], "  $a = 1;");
+    ///
+    /// assert_eq!(line["children"][0]["level"], "note");
+    /// assert_eq!(line["children"][0]["message"], "consider removing it");
+    /// assert!(line["rendered"].as_str().unwrap().starts_with("warning[W0001]: unused variable `$a`"));
+    /// ```
+    pub fn write_rustc_json<W: Write>(
+        &self,
+        mut w: W,
+        reportable: &dyn Reportable,
+    ) -> Result<(), Error> {
+        for report in reportable.to_reports() {
+            let (issues, _) = self.visible_issues(report);
+            for issue in issues {
+                let issue = resolve_origins(issue, report.default_origin.as_deref())?;
+
+                let mut spans = issue
+                    .annotations
+                    .iter()
+                    .flat_map(|annotation| {
+                        annotation.spans().filter_map(|(from, to)| {
+                            self.rustc_span(
+                                &annotation.origin,
+                                from,
+                                to,
+                                annotation.r#type == AnnotationType::Primary,
+                                annotation.message.as_deref(),
+                            )
+                        })
+                    })
+                    .collect::<Vec<serde_json::Value>>();
+                if let Some((origin, from, to)) = &issue.source {
+                    spans.extend(self.rustc_span(origin, *from, *to, true, None));
+                }
+
+                let children = issue
+                    .notes
+                    .iter()
+                    .map(|note| {
+                        serde_json::json!({
+                            "message": note,
+                            "code": null,
+                            "level": "note",
+                            "spans": [],
+                            "children": [],
+                            "rendered": null,
+                        })
+                    })
+                    .collect::<Vec<serde_json::Value>>();
+
+                let mut rendered = String::new();
+                self.render_to_fmt(&mut rendered, &Report::from(issue.clone().into_owned()))?;
+
+                let value = serde_json::json!({
+                    "message": issue.message,
+                    "code": issue.code.as_ref().map(|code| serde_json::json!({
+                        "code": code,
+                        "explanation": null,
+                    })),
+                    "level": match issue.severity {
+                        IssueSeverity::Bug => "error: internal compiler error".to_string(),
+                        severity => severity.to_string(),
+                    },
+                    "spans": spans,
+                    "children": children,
+                    "rendered": rendered,
+                });
+
+                serde_json::to_writer(&mut w, &value).map_err(json_error)?;
+                writeln!(w).map_err(Error::Io)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the `rustc` JSON object of the given span, or `None` if the span can not be
+    /// located in its source.
+    fn rustc_span(
+        &self,
+        origin: &str,
+        from: usize,
+        to: usize,
+        is_primary: bool,
+        label: Option<&str>,
+    ) -> Option<serde_json::Value> {
+        let content = self.content(origin).filter(|_| from <= to)?;
+        let (line_start, column_start) = self.locate(origin, from)?;
+        let (line_end, column_end) = self.locate(origin, to)?;
+
+        let first = content[..from].rfind('\n').map_or(0, |i| i + 1);
+        let last = content[to..].find('\n').map_or(content.len(), |i| to + i);
+        let lines = content[first..last].split('\n').collect::<Vec<&str>>();
+        let text = lines
+            .iter()
+            .enumerate()
+            .map(|(i, line)| {
+                serde_json::json!({
+                    "text": line,
+                    "highlight_start": if i == 0 { column_start } else { 1 },
+                    "highlight_end": if i == lines.len() - 1 {
+                        column_end
+                    } else {
+                        line.chars().count() + 1
+                    },
+                })
+            })
+            .collect::<Vec<serde_json::Value>>();
+
+        let (_, byte_offset) = self.source_offset(origin);
+
+        Some(serde_json::json!({
+            "file_name": origin,
+            "byte_start": from + byte_offset,
+            "byte_end": to + byte_offset,
+            "line_start": line_start,
+            "line_end": line_end,
+            "column_start": column_start,
+            "column_end": column_end,
+            "is_primary": is_primary,
+            "text": text,
+            "label": label,
+            "suggested_replacement": null,
+            "suggestion_applicability": null,
+            "expansion": null,
+        }))
+    }
+
+    /// Returns what rendering the report would produce, without rendering it.
+    ///
+    /// The plan lists the issues that would be rendered, in rendering order, the issues
+    /// that would be skipped along with the reason, and the footers with the notes they
+    /// would be rendered with, including the summary.
+    ///
+    /// `SkipReason::Truncated` only covers issues cut by `ReportBuilder::with_top`. The cap
+    /// of `ReportBuilder::with_max_output_bytes` depends on the rendered output, so issues
+    /// it would cut are still listed as rendered.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// # use ara_source::SourceMap;
+    /// # use ara_reporting::builder::ReportBuilder;
+    /// # use ara_reporting::builder::SkipReason;
+    /// # use ara_reporting::issue::Issue;
+    /// # use ara_reporting::issue::IssueSeverity;
+    /// # use ara_reporting::Report;
+    /// # use ara_reporting::ReportFooter;
+    /// # let source = SourceMap::new(vec![]);
+    /// let report = Report::new()
+    ///     .with_issue(Issue::error("E0001", "rendered").with_source("src/main.ara", 0, 1))
+    ///     .with_issue(Issue::note("N0001", "below threshold").with_source("vendor/foo.ara", 0, 1))
+    ///     .with_issue(Issue::warning("W0001", "hidden").with_source("src/main.ara", 0, 1).hidden())
+    ///     .with_issue(Issue::help("H0001", "truncated").with_source("src/main.ara", 0, 1))
+    ///     .with_footer(ReportFooter::new("done"));
+    ///
+    /// let plan = ReportBuilder::new(&source)
+    ///     .with_origin_min_severity("vendor/**", IssueSeverity::Warning)
+    ///     .with_top(Some(1))
+    ///     .plan(&report);
+    ///
+    /// assert_eq!(plan.rendered.len(), 1);
+    /// assert_eq!(plan.rendered[0].message, "rendered");
+    ///
+    /// let skipped = plan
+    ///     .skipped
+    ///     .iter()
+    ///     .map(|(issue, reason)| (issue.message.as_str(), reason.clone()))
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(skipped, vec![
+    ///     ("below threshold", SkipReason::BelowThreshold),
+    ///     ("hidden", SkipReason::Hidden),
+    ///     ("truncated", SkipReason::Truncated),
+    /// ]);
+    ///
+    /// assert_eq!(plan.footers[0].message, "done");
+    /// assert_eq!(plan.footers[0].notes, vec![
+    ///     "summary: 1 note(s), 1 help(s), 1 warning(s), 1 error(s), 1 hidden".to_string(),
+    /// ]);
+    /// ```
+    pub fn plan(&self, reportable: &dyn Reportable) -> RenderPlan {
+        let mut plan = RenderPlan {
+            rendered: vec![],
+            skipped: vec![],
+            footers: vec![],
+        };
+
+        for report in reportable.to_reports() {
+            let (issues, _) = self.visible_issues(report);
+
+            for issue in &report.issues {
+                let reason = match self.skip_reason(issue) {
+                    Some(reason) => reason,
+                    None if issues.iter().any(|visible| std::ptr::eq(*visible, issue)) => {
+                        continue;
+                    }
+                    None => SkipReason::Truncated,
+                };
+
+                plan.skipped.push((issue.clone(), reason));
+            }
+
+            plan.rendered.extend(issues.into_iter().cloned());
+
+            if let Some(footer) = &report.footer {
+                let counts = self.summary_counts(&report.issues);
+                if is_footer_shown(&counts, footer) {
+                    plan.footers.push(self.planned_footer(&counts, footer));
+                }
+            }
+        }
+
+        if let Some((counts, footer)) = self.trailing_counts(reportable) {
+            if is_footer_shown(&counts, footer) {
+                plan.footers.push(self.planned_footer(&counts, footer));
+            }
+        }
+
+        plan
+    }
+
+    /// Render the report once per target, each in the format of its target.
+    ///
+    /// All formats share the same filtering, so the issues of the human output are exactly
+    /// the issues of the machine-readable outputs.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// # use ara_source::source::Source;
+    /// # use ara_source::source::SourceKind;
+    /// # use ara_source::SourceMap;
+    /// # use ara_reporting::builder::OutputFormat;
+    /// # use ara_reporting::builder::OutputTarget;
+    /// # use ara_reporting::builder::ReportBuilder;
+    /// # use ara_reporting::issue::Issue;
+    /// # use ara_reporting::Report;
+    /// use termcolor::NoColor;
+    ///
+    /// let source = SourceMap::new(vec![
+    ///     Source::new(SourceKind::Script, "main.ara", "function main(): void {}"),
+    /// ]);
+    ///
+    /// let report = Report::new()
+    ///     .with_issue(Issue::error("E0001", "visible").with_source("main.ara", 9, 13))
+    ///     .with_issue(Issue::warning("W0001", "hidden").hidden());
+    ///
+    /// let mut human = NoColor::new(vec![]);
+    /// let mut json = NoColor::new(vec![]);
+    /// ReportBuilder::new(&source)
+    ///     .emit_all(&report, &mut [
+    ///         OutputTarget { format: OutputFormat::Human, writer: &mut human },
+    ///         OutputTarget { format: OutputFormat::Json, writer: &mut json },
+    ///     ])
+    ///     .unwrap();
+    ///
+    /// let human = String::from_utf8(human.into_inner()).unwrap();
+    /// assert!(human.starts_with("error[E0001]: visible"));
+    /// assert!(!human.contains("hidden"));
+    ///
+    /// let json: serde_json::Value = serde_json::from_slice(&json.into_inner()).unwrap();
+    /// assert_eq!(json["reports"][0]["issues"].as_array().unwrap().len(), 1);
+    /// assert_eq!(json["reports"][0]["issues"][0]["message"], "visible");
+    /// ```
+    pub fn emit_all(
+        &self,
+        reportable: &dyn Reportable,
+        targets: &mut [OutputTarget],
+    ) -> Result<(), Error> {
+        for target in targets {
+            match target.format {
+                OutputFormat::Human => self.write(&mut target.writer, reportable)?,
+                OutputFormat::Json => self.write_json(&mut target.writer, reportable)?,
+                OutputFormat::Ndjson => self.write_ndjson(&mut target.writer, reportable)?,
+                OutputFormat::RustcJson => self.write_rustc_json(&mut target.writer, reportable)?,
+                OutputFormat::CodeQuality => {
+                    let report = self.as_code_quality(reportable)?;
+
+                    writeln!(target.writer, "{report}").map_err(Error::Io)?
+                }
+                OutputFormat::Sarif => {
+                    let log = self.as_sarif(reportable)?;
+
+                    writeln!(target.writer, "{log}").map_err(Error::Io)?
+                }
+                OutputFormat::GitHub => {
+                    let commands = self.as_workflow_commands(reportable)?;
+
+                    write!(target.writer, "{commands}").map_err(Error::Io)?
+                }
+                OutputFormat::Checkstyle => {
+                    let document = self.as_checkstyle(reportable)?;
+
+                    write!(target.writer, "{document}").map_err(Error::Io)?
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Write the visible issues of each report as a JSON array of reports, wrapped in an
+    /// envelope holding the schema version, see `Report::from_json`.
+    fn write_json<W: Write>(&self, mut w: W, reportable: &dyn Reportable) -> Result<(), Error> {
+        let envelope = serde_json::json!({
+            "schema_version": REPORT_SCHEMA_VERSION,
+            "reports": self.visible_reports(reportable)?,
+        });
+
+        serde_json::to_writer(&mut w, &envelope).map_err(json_error)?;
+        writeln!(w).map_err(Error::Io)
+    }
+
+    /// Returns the reports, each with only its visible issues, and their origins resolved.
+    fn visible_reports(&self, reportable: &dyn Reportable) -> Result<Vec<Report>, Error> {
+        let mut reports = vec![];
+        for report in reportable.to_reports() {
+            let (issues, _) = self.visible_issues(report);
+
+            let mut visible = report.clone();
+            visible.issues = issues
+                .into_iter()
+                .map(|issue| {
+                    resolve_origins(issue, report.default_origin.as_deref()).map(Cow::into_owned)
+                })
+                .collect::<Result<Vec<Issue>, Error>>()?;
+
+            reports.push(visible);
+        }
+
+        Ok(reports)
+    }
+
+    /// Returns the given footer, with the notes it would be rendered with.
+    fn planned_footer(&self, counts: &SummaryCounts, footer: &ReportFooter) -> ReportFooter {
+        ReportFooter {
+            message: footer.message.clone(),
+            notes: self.plain_footer_notes(counts, footer),
+            summary: false,
+            severity: footer.severity,
+            severity_bar: false,
+            show_when_empty: true,
+        }
+    }
+
+    /// Returns the `"type": "summary"` object of the given footer, for JSON output.
+    fn summary_value(&self, counts: &SummaryCounts, footer: &ReportFooter) -> serde_json::Value {
+        let mut severities = serde_json::Map::new();
+        for (severity, count) in &counts.severities {
+            severities.insert(severity.to_string(), (*count).into());
+        }
+
+        serde_json::json!({
+            "type": "summary",
+            "message": footer.message,
+            "notes": self.plain_footer_notes(counts, footer),
+            "counts": severities,
+            "hidden": counts.hidden,
+        })
+    }
+
+    /// Returns the counts of the given issues, as summarized by footers.
+    fn summary_counts(&self, issues: &[Issue]) -> SummaryCounts {
+        let mut counts = SummaryCounts::default();
+        for issue in issues {
+            self.count(&mut counts, issue);
+        }
+
+        counts
+    }
+
+    /// Counts the given issue, along with whether it is outside of the region filter.
+    pub(crate) fn count(&self, counts: &mut SummaryCounts, issue: &Issue) {
+        counts.add(issue);
+
+        if !issue.hidden && !self.is_in_region(issue) {
+            counts.outside_region += 1;
+        }
+    }
+
+    /// Returns the trailing footer of the given reportable, along with the counts of the
+    /// issues of all of its reports, for the footer to summarize.
+    fn trailing_counts<'r>(
+        &self,
+        reportable: &'r dyn Reportable,
+    ) -> Option<(SummaryCounts, &'r ReportFooter)> {
+        let footer = reportable.trailing_footer()?;
+
+        let mut counts = SummaryCounts::default();
+        for report in reportable.to_reports() {
+            for issue in &report.issues {
+                self.count(&mut counts, issue);
+            }
+        }
+
+        Some((counts, footer))
+    }
+
+    pub(crate) fn config(&self) -> Config {
+        let mut styles = Styles::default();
+
+        styles.secondary_label.set_bold(true);
+        styles.line_number.set_fg(Some(Color::Ansi256(8)));
+        styles.source_border.set_fg(Some(Color::Ansi256(8)));
+
+        let chars = match self.charset {
+            CharSet::Ascii => Chars::ascii(),
+            CharSet::Unicode => Chars::box_drawing(),
+        };
+
+        if let Some(config) = &self.config {
+            let mut config = config.clone();
+            if self.config_styles {
+                config.styles = styles;
+                config.chars = chars;
+            }
+
+            return config;
+        }
+
+        Config {
+            display_style: match self.style {
+                DisplayStyle::Default => CodespanDisplayStyle::Rich,
+                DisplayStyle::Comfortable => CodespanDisplayStyle::Medium,
+                DisplayStyle::Compact | DisplayStyle::Inline => CodespanDisplayStyle::Short,
+            },
+            chars,
+            tab_width: self.tab_width,
+            styles,
+            start_context_lines: self.start_context_lines,
+            end_context_lines: self.end_context_lines,
+        }
+    }
+
+    pub(crate) fn files(&self) -> (SimpleFiles<&'a str, &'a String>, FxHashMap<String, usize>) {
+        let mut files = SimpleFiles::new();
+        let mut files_ids = FxHashMap::default();
+        self.source_map.sources.iter().for_each(|source| {
+            files_ids.insert(
+                source.name().to_string(),
+                files.add(source.name(), &source.content),
+            );
+        });
+
+        (files, files_ids)
+    }
+
+    pub(crate) fn write_issue<'f, T: WriteColor, F: Files<'f, FileId = usize>, S: BuildHasher>(
+        &self,
+        w: &mut T,
+        config: &Config,
+        files: &'f F,
+        files_ids: &HashMap<String, usize, S>,
+        issue: &Issue,
+    ) -> Result<(), Error> {
+        if self.style == DisplayStyle::Inline {
+            return self.write_inline(w, config, issue).map_err(Error::Io);
+        }
+
+        let diagnostic = self.diagnostic(issue, files_ids);
+        let runs = self.annotation_group_runs(issue);
+        let notes = diagnostic
+            .notes
+            .iter()
+            .filter(|_| self.styled_notes)
+            .map(|note| style_note_code(note, &config.styles))
+            .filter(|note| note.iter().any(|(_, style)| style.role.is_some()))
+            .collect::<Vec<StyledLine>>();
+        let files = OffsetFiles {
+            files,
+            line_offsets: self
+                .source_offsets
+                .iter()
+                .filter_map(|(origin, line_offset, _)| {
+                    files_ids.get(origin).map(|id| (*id, *line_offset))
+                })
+                .collect(),
+        };
+
+        let continued = diagnostic
+            .labels
+            .iter()
+            .filter_map(|label| label.message.split_once('\n'))
+            .collect::<Vec<(&str, &str)>>();
+
+        if self.style == DisplayStyle::Compact {
+            return self.write_compact(w, config, &files, &diagnostic, issue);
+        }
+
+        let id = self.header_id(issue);
+
+        if self.wrap_code_frames
+            || !continued.is_empty()
+            || !runs.is_empty()
+            || !notes.is_empty()
+            || id.is_some()
+        {
+            let mut lines = StyledLinesWriter::new(&config.styles);
+            self.emit(&mut lines, config, &files, &diagnostic)?;
+
+            let mut lines = lines.into_lines();
+            if let Some(id) = id {
+                insert_header_id(&mut lines, &id);
+            }
+
+            let lines = style_notes(lines, &notes, config.chars.note_bullet);
+            let lines = self.fill_group_breaks(lines, config, &runs);
+            let mut lines = attach_label_continuations(lines, &continued);
+            if self.wrap_code_frames {
+                lines = wrap_frame_lines(lines, terminal_width());
+            }
+
+            return styled::write_lines(w, &lines, &config.styles).map_err(Error::Io);
+        }
+
+        self.emit(w, config, &files, &diagnostic)
+    }
+
+    /// Write the given issue as a header, followed by its source line and a caret line.
+    fn write_inline<T: WriteColor>(
+        &self,
+        w: &mut T,
+        config: &Config,
+        issue: &Issue,
+    ) -> std::io::Result<()> {
+        let location = primary_span(issue).and_then(|(origin, from, to)| {
+            let content = self.content(origin)?;
+            let line_start = content.get(..from)?.rfind('\n').map_or(0, |i| i + 1);
+            let line_end = content[from..]
+                .find('\n')
+                .map_or(content.len(), |i| from + i);
+
+            Some((
+                origin,
+                content,
+                line_start,
+                line_end,
+                from,
+                to.min(line_end),
+            ))
+        });
+
+        if let Some((origin, _, _, _, from, _)) = location {
+            if let Some((line, column)) = self.locate(origin, from) {
+                write!(w, "{origin}:{line}:{column} ")?;
+            }
+        }
+
+        self.write_header(w, config, issue, &issue.message.replace('\n', " "))?;
+
+        let severity = issue.severity.into();
+
+        if let Some((_, content, line_start, line_end, from, to)) = location {
+            let padding = content[line_start..from]
+                .chars()
+                .map(|c| if c == '\t' { '\t' } else { ' ' })
+                .collect::<String>();
+            let width = content.get(from..to).map_or(0, |span| span.chars().count());
+
+            writeln!(w, "{}", &content[line_start..line_end])?;
+            write!(w, "{padding}")?;
+            w.set_color(config.styles.label(severity, LabelStyle::Primary))?;
+            write!(w, "{}", "^".repeat(width.max(1)))?;
+            w.reset()?;
+            writeln!(w)?;
+        }
+
+        Ok(())
+    }
+
+    /// Write the header of the given issue with the given message, its severity written
+    /// with the severity prefix of the builder.
+    fn write_header<T: WriteColor>(
+        &self,
+        w: &mut T,
+        config: &Config,
+        issue: &Issue,
+        message: &str,
+    ) -> std::io::Result<()> {
+        let severity = issue.severity.into();
+        w.set_color(config.styles.header(severity))?;
+        match self.severity_prefix {
+            SeverityPrefix::Word => write!(w, "{}", issue.severity)?,
+            // `warning` is the longest severity word.
+            SeverityPrefix::Padded => write!(w, "{:<7}", issue.severity.to_string())?,
+            SeverityPrefix::Symbol => write!(
+                w,
+                "[{}]",
+                match issue.severity {
+                    IssueSeverity::Note => 'N',
+                    IssueSeverity::Help => 'H',
+                    IssueSeverity::Warning => 'W',
+                    IssueSeverity::Error => 'E',
+                    IssueSeverity::Bug => 'B',
+                }
+            )?,
+        }
+        if let Some(code) = issue.code.as_ref().filter(|_| self.show_codes) {
+            write!(w, "[{code}]")?;
+        }
+        if let Some(id) = self.header_id(issue) {
+            write!(w, "{id}")?;
+        }
+        w.set_color(&config.styles.header_message)?;
+        write!(w, ": {message}")?;
+        w.reset()?;
+        writeln!(w)?;
+
+        Ok(())
+    }
+
+    /// Write the given diagnostic as one header per primary label, prefixed with the
+    /// location of the label, like codespan renders short diagnostics.
+    fn write_compact<'f, T: WriteColor, F: Files<'f, FileId = usize>>(
+        &self,
+        w: &mut T,
+        config: &Config,
+        files: &'f F,
+        diagnostic: &Diagnostic<usize>,
+        issue: &Issue,
+    ) -> Result<(), Error> {
+        let mut located = false;
+        for label in &diagnostic.labels {
+            if label.style != LabelStyle::Primary {
+                continue;
+            }
+
+            let name = files.name(label.file_id).map_err(codespan_error)?;
+            let location = files
+                .location(label.file_id, label.range.start)
+                .map_err(codespan_error)?;
+
+            write!(
+                w,
+                "{name}:{}:{}: ",
+                location.line_number, location.column_number
+            )
+            .and_then(|_| self.write_header(w, config, issue, &diagnostic.message))
+            .map_err(Error::Io)?;
+            located = true;
+        }
+
+        if !located {
+            self.write_header(w, config, issue, &diagnostic.message)
+                .map_err(Error::Io)?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns whether the given issue is rendered with both primary and secondary labels.
+    fn mixes_label_styles<S: BuildHasher>(
+        &self,
+        issue: &Issue,
+        files_ids: &HashMap<String, usize, S>,
+    ) -> bool {
+        // compact issues are rendered without carets.
+        if matches!(self.style, DisplayStyle::Compact | DisplayStyle::Inline) {
+            return false;
+        }
+
+        let labels = self.diagnostic(issue, files_ids).labels;
+
+        labels
+            .iter()
+            .any(|label| label.style == LabelStyle::Primary)
+            && labels
+                .iter()
+                .any(|label| label.style == LabelStyle::Secondary)
+    }
+
+    /// Returns the color choice to render with, `is_terminal` tells whether the output is
+    /// a terminal, see `ReportBuilder::with_colors`.
+    fn resolve_color_choice(&self, is_terminal: bool) -> TermColorChoice {
+        let always = match self.charset {
+            CharSet::Ascii => TermColorChoice::AlwaysAnsi,
+            CharSet::Unicode => TermColorChoice::Always,
+        };
+
+        match self.colors {
+            ColorChoice::Always => always,
+            ColorChoice::Never => TermColorChoice::Never,
+            ColorChoice::Auto => {
+                let var = |name| std::env::var(name).ok();
+
+                if var("CLICOLOR_FORCE").is_some_and(|value| value != "0") {
+                    always
+                } else if var("NO_COLOR").is_some_and(|value| !value.is_empty())
+                    || var("CLICOLOR").is_some_and(|value| value == "0")
+                    || !is_terminal
+                {
+                    TermColorChoice::Never
+                } else {
+                    TermColorChoice::Auto
+                }
+            }
+        }
+    }
+
+    /// Write the banner of the given origin group.
+    fn write_file_banner<T: WriteColor>(
+        &self,
+        w: &mut T,
+        config: &Config,
+        origin: &str,
+        issues: usize,
+    ) -> Result<(), Error> {
+        let banner = match self.charset {
+            CharSet::Ascii => format!("== {origin} - {issues} issue(s)"),
+            CharSet::Unicode => format!("━━ {origin} — {issues} issue(s)"),
+        };
+
+        w.set_color(Role::FileBanner.spec(&config.styles))
+            .and_then(|_| write!(w, "{banner}"))
+            .and_then(|_| w.reset())
+            .and_then(|_| writeln!(w))
+            .map_err(Error::Io)
+    }
+
+    pub(crate) fn write_footer<'f, T: WriteColor, F: Files<'f, FileId = usize>>(
+        &self,
+        w: &mut T,
+        config: &Config,
+        files: &'f F,
+        counts: &SummaryCounts,
+        footer: &ReportFooter,
+    ) -> Result<(), Error> {
+        if !is_footer_shown(counts, footer) {
+            return Ok(());
+        }
+
+        let notes = self.footer_notes(counts, footer, &config.styles);
+
+        let mut lines = StyledLinesWriter::new(&config.styles);
+        self.render_footer(
+            &mut lines,
+            config,
+            files,
+            &footer.message,
+            &notes.iter().map(styled::text).collect::<Vec<String>>(),
+            footer.severity.or(counts.severity),
+        )?;
+
+        let lines = style_notes(lines.into_lines(), &notes, config.chars.note_bullet);
+
+        styled::write_lines(w, &lines, &config.styles).map_err(Error::Io)
+    }
+
+    /// Render a footer with the given message and notes.
+    fn render_footer<'f, T: WriteColor, F: Files<'f, FileId = usize>>(
+        &self,
+        w: &mut T,
+        config: &Config,
+        files: &'f F,
+        message: &str,
+        notes: &[String],
+        severity: Option<IssueSeverity>,
+    ) -> Result<(), Error> {
+        // a footer without a message only shows its notes, without an empty header.
+        if message.is_empty() {
+            let marker = if self.compact_footer { "  =" } else { " =" };
+            let indentation = format!("\n{}", " ".repeat(marker.len() + 1));
+            for note in notes {
+                let note = note.lines().collect::<Vec<&str>>().join(&indentation);
+                writeln!(w, "{marker} {note}").map_err(Error::Io)?;
+            }
+            if !self.compact_footer {
+                writeln!(w).map_err(Error::Io)?;
+            }
+
+            return Ok(());
+        }
+
+        if self.compact_footer {
+            writeln!(w, "{message}").map_err(Error::Io)?;
+            for note in notes {
+                writeln!(w, "  = {note}").map_err(Error::Io)?;
+            }
+
+            return Ok(());
+        }
+
+        let severity = severity.unwrap_or(IssueSeverity::Error);
+        // align continuation lines under the first line, after the `severity: ` header.
+        let indentation = format!("\n{}", " ".repeat(severity.to_string().len() + 2));
+
+        let diagnostic = Diagnostic::new(severity.into())
+            .with_message(message.lines().collect::<Vec<&str>>().join(&indentation))
+            .with_notes(notes.to_vec());
+
+        self.emit(w, config, files, &diagnostic)
+    }
+
+    pub(crate) fn emit<'f, T: WriteColor, F: Files<'f, FileId = usize>>(
+        &self,
+        w: &mut T,
+        config: &Config,
+        files: &'f F,
+        diagnostic: &Diagnostic<usize>,
+    ) -> Result<(), Error> {
+        emit(w, config, files, diagnostic).map_err(codespan_error)
+    }
+
+    pub(crate) fn write_separator<T: WriteColor>(&self, w: &mut T) -> Result<(), Error> {
+        match self.separator {
+            Separator::None => Ok(()),
+            Separator::BlankLine => writeln!(w).map_err(Error::Io),
+            Separator::Rule(character) => {
+                writeln!(w, "{}", character.to_string().repeat(terminal_width())).map_err(Error::Io)
+            }
+        }
+    }
+
+    pub(crate) fn diagnostic<S: BuildHasher>(
+        &self,
+        issue: &Issue,
+        files_ids: &HashMap<String, usize, S>,
+    ) -> Diagnostic<usize> {
+        let markers = match self.annotation_messages {
+            AnnotationMessages::Inline => self
+                .underline_markers(issue)
+                .into_iter()
+                .map(|(i, marker)| (i, format!("[{marker}]")))
+                .collect::<FxHashMap<usize, String>>(),
+            AnnotationMessages::Footnotes => issue
+                .annotations
+                .iter()
+                .enumerate()
+                .filter(|(_, annotation)| annotation.message.is_some())
+                .enumerate()
+                .map(|(position, (i, _))| (i, format!("[{}]", position + 1)))
+                .collect::<FxHashMap<usize, String>>(),
+        };
+
+        // codespan can not render a multi-line header, the lines following the first one
+        // are rendered as notes, before the issue notes.
+        let mut lines = issue.message.lines();
+        let header = lines.next().unwrap_or_default();
+
+        let mut notes = lines.map(str::to_string).collect::<Vec<String>>();
+        notes.extend(issue.notes.iter().map(|note| self.normalize_note(note)));
+        for (i, annotation) in issue.annotations.iter().enumerate() {
+            if let (Some(marker), Some(message)) = (markers.get(&i), &annotation.message) {
+                match &annotation.note {
+                    Some(note) => notes.push(format!("{marker} {message}\n{note}")),
+                    None => notes.push(format!("{marker} {message}")),
+                }
+            }
+        }
+        notes.extend(self.global_note.iter().cloned());
+
+        // without a source or a primary annotation, the first annotation anchors the frame.
+        let anchor = (issue.source.is_none()
+            && issue
+                .annotations
+                .iter()
+                .all(|annotation| annotation.r#type != AnnotationType::Primary))
+        .then_some(0);
+
+        let single_primary = self.single_primary && issue.source.is_some();
+        let is_source = |annotation: &Annotation| {
+            issue.source.as_ref().is_some_and(|(origin, from, to)| {
+                annotation.origin == *origin && annotation.from == *from && annotation.to == *to
+            })
+        };
+        // see `ReportBuilder::with_single_primary` for how the source label is reconciled
+        // with primary annotations.
+        let merged = issue.annotations.iter().any(|annotation| {
+            annotation.r#type == AnnotationType::Primary
+                && (!self.single_primary || is_source(annotation))
+        });
+
+        let mut diagnostic = Diagnostic::new(issue.severity.into())
+            .with_message(header)
+            .with_notes(notes)
+            .with_labels(
+                issue
+                    .annotations
+                    .iter()
+                    .enumerate()
+                    .flat_map(|(i, annotation)| {
+                        let mut label = Label::new(
+                            match annotation.r#type {
+                                AnnotationType::Primary
+                                    if single_primary && !is_source(annotation) =>
+                                {
+                                    LabelStyle::Secondary
+                                }
+                                AnnotationType::Primary => LabelStyle::Primary,
+                                AnnotationType::Secondary if anchor == Some(i) => {
+                                    LabelStyle::Primary
+                                }
+                                AnnotationType::Secondary => LabelStyle::Secondary,
+                            },
+                            *files_ids.get(&annotation.origin).unwrap_or(&0),
+                            self.range(&annotation.origin, annotation.from, annotation.to),
+                        );
+
+                        if let Some(marker) = markers.get(&i) {
+                            label = label.with_message(marker);
+                        } else {
+                            match (&annotation.message, &annotation.note) {
+                                (Some(message), Some(note)) => {
+                                    label = label.with_message(format!("{message}\n{note}"));
+                                }
+                                (Some(message), None) | (None, Some(message)) => {
+                                    label = label.with_message(message);
+                                }
+                                (None, None) => {}
+                            }
+                        }
+
+                        // the additional ranges share the message and style of the annotation.
+                        let mut labels = vec![label.clone()];
+                        for (from, to) in &annotation.ranges {
+                            let mut label = label.clone();
+                            label.range = self.range(&annotation.origin, *from, *to);
+                            labels.push(label);
+                        }
+
+                        labels
+                    })
+                    .collect(),
+            );
+
+        if let Some(code) = issue.code.as_ref().filter(|_| self.show_codes) {
+            diagnostic = diagnostic.with_code(code);
+        }
+
+        if let Some((source, from, to)) = issue.source.as_ref().filter(|_| !merged) {
+            diagnostic = diagnostic.with_labels(vec![Label::primary(
+                *files_ids.get(source).unwrap_or(&0),
+                self.range(source, *from, *to),
+            )])
+        }
+
+        diagnostic
+    }
+
+    /// Returns the runs of close annotations of each annotation group of the given issue, as
+    /// their origin along with their first and last 0-based line index.
+    ///
+    /// A run ends when the next annotation of the group is more than
+    /// `ANNOTATION_GROUP_MAX_GAP` lines away, runs on a single line are omitted.
+    fn annotation_group_runs<'i>(&self, issue: &'i Issue) -> Vec<(&'i str, usize, usize)> {
+        let mut members = issue
+            .annotations
+            .iter()
+            .filter_map(|annotation| Some((annotation.group?, annotation)))
+            .flat_map(|(group, annotation)| {
+                annotation.spans().filter_map(move |(from, to)| {
+                    let content = self.content(&annotation.origin)?;
+                    let start = content.get(..from)?.matches('\n').count();
+                    let end = content.get(..to)?.matches('\n').count();
+
+                    Some((group, annotation.origin.as_str(), start, end))
+                })
+            })
+            .collect::<Vec<(usize, &str, usize, usize)>>();
+        members.sort();
+
+        let mut runs = vec![];
+        let mut run: Option<(usize, &str, usize, usize)> = None;
+        for (group, origin, start, end) in members {
+            match &mut run {
+                Some((run_group, run_origin, _, run_end))
+                    if *run_group == group
+                        && *run_origin == origin
+                        && start <= *run_end + ANNOTATION_GROUP_MAX_GAP + 1 =>
+                {
+                    *run_end = (*run_end).max(end);
+                }
+                _ => {
+                    if let Some((_, origin, start, end)) = run {
+                        if start < end {
+                            runs.push((origin, start, end));
+                        }
+                    }
+
+                    run = Some((group, origin, start, end));
+                }
+            }
+        }
+
+        if let Some((_, origin, start, end)) = run {
+            if start < end {
+                runs.push((origin, start, end));
+            }
+        }
+
+        runs
+    }
 
-            diagnostics.push(diagnostic);
-        }
+    /// Replaces the breaks of the given code frame lines that fall within one of the given
+    /// annotation group runs with the source lines they omit, so that each run is rendered
+    /// in one continuous frame.
+    fn fill_group_breaks(
+        &self,
+        lines: Vec<StyledLine>,
+        config: &Config,
+        runs: &[(&str, usize, usize)],
+    ) -> Vec<StyledLine> {
+        let texts = lines.iter().map(styled::text).collect::<Vec<String>>();
+        let numbers = texts
+            .iter()
+            .map(|text| frame_line_number(text, config.chars.source_border_left))
+            .collect::<Vec<Option<usize>>>();
 
-        if let Some(footer) = &report.footer {
-            let mut notes = footer.notes.clone();
+        let mut filled = vec![];
+        let mut origin = None;
+        let mut previous = None;
+        for (i, line) in lines.into_iter().enumerate() {
+            let text = texts[i].trim_start();
+            if let Some(locus) = text.strip_prefix(config.chars.snippet_start.as_str()) {
+                origin = locus.trim_start().rsplitn(3, ':').nth(2);
+                previous = None;
+            }
 
-            if footer.summary {
-                let mut entries = FxHashMap::default();
-                report.issues.iter().for_each(|issue| {
-                    *entries.entry(issue.severity).or_insert(0) += 1;
-                });
+            if numbers[i].is_some() {
+                previous = numbers[i];
+            }
 
-                let mut entries = entries.iter().collect::<Vec<(&IssueSeverity, &usize)>>();
-                entries.sort_by_key(|severity| *severity);
+            let padding = texts[i].len() - text.len();
+            let omitted = origin.zip(previous).and_then(|(origin, previous)| {
+                if padding == 0 || !text.starts_with(config.chars.source_border_left_break) {
+                    return None;
+                }
 
-                let summary = entries
+                let next = numbers[i..].iter().flatten().next()?;
+                let content = self.content(origin)?;
+                let offset = self
+                    .source_offsets
                     .iter()
-                    .map(|(severity, count)| format!("{count} {severity}(s)"))
-                    .collect::<Vec<String>>()
-                    .join(", ");
+                    .find(|(offset_origin, _, _)| offset_origin == origin)
+                    .map_or(0, |(_, line_offset, _)| *line_offset);
+
+                runs.iter()
+                    .any(|(run_origin, start, end)| {
+                        *run_origin == origin
+                            && start + offset < previous
+                            && *next <= end + offset + 1
+                    })
+                    .then(|| {
+                        content
+                            .split('\n')
+                            .enumerate()
+                            .skip(previous - offset)
+                            .take(next - previous - 1)
+                            .map(|(index, source)| (index + offset + 1, source))
+                            .collect::<Vec<(usize, &str)>>()
+                    })
+            });
+
+            let Some(omitted) = omitted else {
+                filled.push(line);
+
+                continue;
+            };
+
+            let inner_gutter = styled::slice(&line, padding + 1, texts[i].chars().count());
+            for (number, source) in omitted {
+                let mut source_line = vec![
+                    (
+                        format!("{number:>width$}", width = padding - 1),
+                        Role::LineNumber.style(&config.styles),
+                    ),
+                    (" ".to_string(), Style::default()),
+                    (
+                        config.chars.source_border_left.to_string(),
+                        Role::SourceBorder.style(&config.styles),
+                    ),
+                ];
+                source_line.extend(inner_gutter.clone());
+                source_line.push((
+                    format!(
+                        " {}",
+                        expand_tabs(source.trim_end_matches('\r'), config.tab_width)
+                    ),
+                    Style::default(),
+                ));
+
+                filled.push(source_line);
+            }
+        }
+
+        filled
+    }
+
+    /// Returns the id of the given issue as rendered in its header, if ids are shown.
+    fn header_id(&self, issue: &Issue) -> Option<String> {
+        issue
+            .id
+            .filter(|_| self.show_ids)
+            .map(|id| format!(" (#{:08x})", id >> 32))
+    }
+
+    /// Returns the issues of the given report that should be rendered, in rendering order,
+    /// along with the number of issues omitted by the `top` limit.
+    pub(crate) fn visible_issues<'b>(&self, report: &'b Report) -> (Vec<&'b Issue>, usize) {
+        let mut issues = report
+            .issues
+            .iter()
+            .filter(|issue| self.skip_reason(issue).is_none())
+            .collect::<Vec<&Issue>>();
+
+        let mut omitted = 0;
+        if let Some(top) = self.top {
+            // stable sort, ties are kept in document order.
+            issues.sort_by_key(|issue| std::cmp::Reverse((issue.severity, issue.weight)));
+            omitted = issues.len().saturating_sub(top);
+            issues.truncate(top);
+        }
+
+        (issues, omitted)
+    }
+
+    /// Returns why the given issue is not rendered, regardless of the `top` limit.
+    fn skip_reason(&self, issue: &Issue) -> Option<SkipReason> {
+        if issue.hidden {
+            Some(SkipReason::Hidden)
+        } else if self.is_suppressed(issue) {
+            Some(SkipReason::BelowThreshold)
+        } else if !self.is_in_region(issue) {
+            Some(SkipReason::OutsideRegion)
+        } else {
+            None
+        }
+    }
+
+    /// Returns whether the source of the given issue overlaps the region filter, if any.
+    pub(crate) fn is_in_region(&self, issue: &Issue) -> bool {
+        let Some((region_origin, region_from, region_to)) = &self.region else {
+            return true;
+        };
+
+        match &issue.source {
+            Some((origin, from, to)) => {
+                // an empty span is in the region when the position it points at is.
+                origin == region_origin
+                    && ranges_overlap((*from, (*to).max(from + 1)), (*region_from, *region_to))
+            }
+            None => false,
+        }
+    }
+
+    /// Returns whether the given issue is below the minimum severity configured for its origin.
+    ///
+    /// When multiple rules match, the most specific one (the one with the most literal
+    /// characters) applies.
+    pub(crate) fn is_suppressed(&self, issue: &Issue) -> bool {
+        let Some(origin) = issue.origin() else {
+            return false;
+        };
+
+        self.origin_thresholds
+            .iter()
+            .filter(|(glob, _)| glob_matches(glob, origin))
+            .max_by_key(|(glob, _)| glob.chars().filter(|c| !matches!(c, '*' | '?')).count())
+            .map(|(_, min)| issue.severity < *min)
+            .unwrap_or(false)
+    }
+
+    /// Returns the 1-based line and column of the given offset in the named source.
+    pub(crate) fn locate(&self, origin: &str, offset: usize) -> Option<(usize, usize)> {
+        let (line, column) = line_column(self.content(origin)?, offset).ok()?;
+        let (line_offset, _) = self.source_offset(origin);
+
+        Some((line + line_offset, column))
+    }
+
+    /// Returns the line and byte offsets of the given source.
+    pub(crate) fn source_offset(&self, origin: &str) -> (usize, usize) {
+        self.source_offsets
+            .iter()
+            .find(|(other, _, _)| other == origin)
+            .map_or((0, 0), |(_, line_offset, byte_offset)| {
+                (*line_offset, *byte_offset)
+            })
+    }
+
+    fn content(&self, origin: &str) -> Option<&str> {
+        source_content(self.source_map, origin)
+    }
+
+    /// Returns the byte range to render for the given span.
+    ///
+    /// When using the lossy encoding policy, offsets that fall inside a UTF-8 code point are
+    /// widened to the surrounding character boundaries.
+    fn range(&self, origin: &str, from: usize, to: usize) -> Range<usize> {
+        if self.encoding_policy == EncodingPolicy::Strict {
+            return from..to;
+        }
+
+        match self.content(origin) {
+            Some(content) => {
+                let mut from = from.min(content.len());
+                while !content.is_char_boundary(from) {
+                    from -= 1;
+                }
 
-                notes.push(format!("summary: {summary}"));
+                let mut to = to;
+                while to < content.len() && !content.is_char_boundary(to) {
+                    to += 1;
+                }
+
+                from..to
             }
+            None => from..to,
+        }
+    }
 
-            diagnostics.push(
-                Diagnostic::new(report.severity().unwrap_or(IssueSeverity::Error).into())
-                    .with_message(&footer.message)
-                    .with_notes(notes),
+    pub(crate) fn check_encoding(&self, issue: &Issue) -> Result<(), Error> {
+        let spans = issue
+            .annotations
+            .iter()
+            .flat_map(|annotation| {
+                std::iter::once((annotation.from, annotation.to))
+                    .chain(annotation.ranges.iter().copied())
+                    .map(|(from, to)| (annotation.origin.as_str(), from, to))
+            })
+            .chain(
+                issue
+                    .source
+                    .iter()
+                    .map(|(origin, from, to)| (origin.as_str(), *from, *to)),
             );
+
+        for (origin, from, to) in spans {
+            if let Some(content) = self.content(origin) {
+                for given in [from, to] {
+                    if given <= content.len() && !content.is_char_boundary(given) {
+                        return Err(Error::InvalidSourceEncoding {
+                            origin: origin.to_string(),
+                            given,
+                        });
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the markers assigned to secondary annotations that share a line with another one.
+    ///
+    /// codespan draws every secondary underline with the same character, so colliding
+    /// annotations are labelled with a distinct marker instead, and their messages are
+    /// listed in a legend below the frame.
+    fn underline_markers(&self, issue: &Issue) -> FxHashMap<usize, char> {
+        let mut markers = FxHashMap::default();
+        if !self.distinct_underlines || self.charset != CharSet::Ascii {
+            return markers;
+        }
+
+        let mut lines: FxHashMap<(&str, usize), Vec<usize>> = FxHashMap::default();
+        for (i, annotation) in issue.annotations.iter().enumerate() {
+            if annotation.r#type != AnnotationType::Secondary || annotation.message.is_none() {
+                continue;
+            }
+
+            if let Some(line) = self
+                .content(&annotation.origin)
+                .and_then(|content| content.get(..annotation.from))
+                .map(|content| content.matches('\n').count())
+            {
+                lines
+                    .entry((annotation.origin.as_str(), line))
+                    .or_default()
+                    .push(i);
+            }
+        }
+
+        let mut colliding = lines
+            .into_values()
+            .filter(|annotations| annotations.len() > 1)
+            .flatten()
+            .collect::<Vec<usize>>();
+        colliding.sort();
+
+        for (position, i) in colliding.into_iter().enumerate() {
+            markers.insert(i, UNDERLINE_MARKERS[position % UNDERLINE_MARKERS.len()]);
+        }
+
+        markers
+    }
+
+    fn normalize_note(&self, note: &str) -> String {
+        match self.note_normalization {
+            NoteNormalization::None => note.to_string(),
+            NoteNormalization::EnsureTrailingPeriod => {
+                if note.is_empty() || note.ends_with(['.', '!', '?']) {
+                    note.to_string()
+                } else {
+                    format!("{note}.")
+                }
+            }
+            NoteNormalization::CapitalizeFirst => {
+                let mut chars = note.chars();
+                match chars.next() {
+                    Some(first) => first.to_uppercase().chain(chars).collect(),
+                    None => String::new(),
+                }
+            }
+        }
+    }
+
+    /// Returns the notes of the given footer, as styled lines.
+    fn footer_notes(
+        &self,
+        counts: &SummaryCounts,
+        footer: &ReportFooter,
+        styles: &Styles,
+    ) -> Vec<StyledLine> {
+        let mut notes = footer
+            .notes
+            .iter()
+            .map(|note| self.normalize_note(note))
+            .collect::<Vec<String>>();
+
+        if self.global_note_in_footer {
+            if let Some(note) = &self.global_note {
+                notes.push(note.clone());
+            }
+        }
+
+        if counts.outside_region > 0 {
+            notes.push(format!(
+                "{} issue(s) outside of the region skipped",
+                counts.outside_region
+            ));
+        }
+
+        let mut notes = notes
+            .into_iter()
+            .map(|note| vec![(note, Style::default())])
+            .collect::<Vec<StyledLine>>();
+
+        if footer.severity_bar && !counts.severities.is_empty() {
+            notes.push(self.severity_bar(counts, styles));
+        }
+
+        if footer.summary {
+            let summary = counts.line(self.summary_order == SummaryOrder::Descending);
+
+            match &self.summary_prefix {
+                Some(prefix) => notes.push(vec![(format!("{prefix} {summary}"), Style::default())]),
+                None => notes.push(vec![(summary, Style::default())]),
+            }
+        }
+
+        notes
+    }
+
+    /// Returns the notes of the given footer, without styles.
+    fn plain_footer_notes(&self, counts: &SummaryCounts, footer: &ReportFooter) -> Vec<String> {
+        self.footer_notes(counts, footer, &Styles::default())
+            .iter()
+            .map(styled::text)
+            .collect()
+    }
+
+    /// Returns a bar showing the proportion of issues of each severity of the given counts,
+    /// highest severity first, followed by the number of issues of each severity.
+    fn severity_bar(&self, counts: &SummaryCounts, styles: &Styles) -> StyledLine {
+        let mut counts = counts
+            .severities
+            .iter()
+            .map(|(severity, count)| (*severity, *count))
+            .collect::<Vec<(IssueSeverity, usize)>>();
+        counts.reverse();
+
+        // largest remainder, so that the cells always add up to the width of the bar.
+        let total = counts.iter().map(|(_, count)| count).sum::<usize>();
+        let mut cells = counts
+            .iter()
+            .map(|(_, count)| count * SEVERITY_BAR_WIDTH / total)
+            .collect::<Vec<usize>>();
+        let mut remainders = (0..counts.len()).collect::<Vec<usize>>();
+        remainders.sort_by_key(|i| std::cmp::Reverse(counts[*i].1 * SEVERITY_BAR_WIDTH % total));
+        let missing = SEVERITY_BAR_WIDTH - cells.iter().sum::<usize>();
+        for i in remainders.into_iter().take(missing) {
+            cells[i] += 1;
+        }
+
+        let mut bar = vec![("[".to_string(), Style::default())];
+        for ((severity, _), cells) in counts.iter().zip(cells) {
+            let cell = match (&self.charset, severity) {
+                (CharSet::Ascii, IssueSeverity::Bug | IssueSeverity::Error) => '#',
+                (CharSet::Ascii, IssueSeverity::Warning) => '=',
+                (CharSet::Ascii, IssueSeverity::Help | IssueSeverity::Note) => '-',
+                (CharSet::Unicode, IssueSeverity::Bug | IssueSeverity::Error) => '█',
+                (CharSet::Unicode, IssueSeverity::Warning) => '▓',
+                (CharSet::Unicode, IssueSeverity::Help | IssueSeverity::Note) => '░',
+            };
+
+            let role = match severity {
+                IssueSeverity::Bug => Role::HeaderBug,
+                IssueSeverity::Error => Role::HeaderError,
+                IssueSeverity::Warning => Role::HeaderWarning,
+                IssueSeverity::Note => Role::HeaderNote,
+                IssueSeverity::Help => Role::HeaderHelp,
+            };
+
+            bar.push((cell.to_string().repeat(cells), role.style(styles)));
+        }
+
+        let counts = counts
+            .iter()
+            .map(|(severity, count)| format!("{count} {severity}(s)"))
+            .collect::<Vec<String>>()
+            .join(" / ");
+        bar.push((format!("] {counts}"), Style::default()));
+
+        bar
+    }
+}
+
+/// Returns the width of the terminal, as set by the `COLUMNS` environment variable.
+fn terminal_width() -> usize {
+    std::env::var("COLUMNS")
+        .ok()
+        .and_then(|columns| columns.parse::<usize>().ok())
+        .filter(|columns| *columns > 0)
+        .unwrap_or(80)
+}
+
+/// Returns the width of the gutter of a code frame line, and whether it holds a line number.
+fn frame_gutter(text: &[char]) -> Option<(usize, bool)> {
+    let border = text.iter().position(|c| *c != ' ' && !c.is_ascii_digit())?;
+    if border == 0 || text[border - 1] != ' ' || !matches!(text[border], '|' | '│') {
+        return None;
+    }
+
+    let width = if text.get(border + 1) == Some(&' ') {
+        border + 2
+    } else {
+        border + 1
+    };
+
+    Some((width, text[..border].iter().any(|c| c.is_ascii_digit())))
+}
+
+/// Returns the line number of the given code frame line, if it is a source line.
+fn frame_line_number(text: &str, border: char) -> Option<usize> {
+    let text = text.trim_start();
+    let digits = text.find(|c: char| !c.is_ascii_digit())?;
+
+    text[digits..]
+        .strip_prefix(' ')
+        .filter(|rest| rest.starts_with(border))
+        .and_then(|_| text[..digits].parse().ok())
+}
+
+/// Returns the given source line with its tabs expanded to the given width, like codespan
+/// renders source lines.
+fn expand_tabs(source: &str, tab_width: usize) -> String {
+    let mut expanded = String::new();
+    for c in source.chars() {
+        match (c, tab_width) {
+            ('\t', 0) => {}
+            ('\t', _) => {
+                let column = expanded.chars().count();
+                expanded.push_str(&" ".repeat(tab_width - column % tab_width));
+            }
+            _ => expanded.push(c),
+        }
+    }
+
+    expanded
+}
+
+/// Soft-wraps the source lines of code frames that are wider than the given width.
+///
+/// The label lines following a wrapped source line are split at the same columns, with the
+/// label message kept next to the end of its underline.
+fn wrap_frame_lines(lines: Vec<StyledLine>, width: usize) -> Vec<StyledLine> {
+    let texts = lines
+        .iter()
+        .map(|line| styled::text(line).chars().collect::<Vec<char>>())
+        .collect::<Vec<Vec<char>>>();
+
+    let mut wrapped = vec![];
+    let mut i = 0;
+    while i < lines.len() {
+        let (gutter, available) = match frame_gutter(&texts[i]) {
+            Some((gutter, true)) if texts[i].len() > width.max(gutter + 1) => {
+                (gutter, width.saturating_sub(gutter).max(1))
+            }
+            _ => {
+                wrapped.push(lines[i].clone());
+                i += 1;
+
+                continue;
+            }
+        };
+
+        let mut labels = vec![];
+        while let Some(text) = texts.get(i + 1 + labels.len()) {
+            if frame_gutter(text) != Some((gutter, false)) || text.len() <= gutter {
+                break;
+            }
+
+            // the underline is drawn up to the first character that is not part of a
+            // drawing, the rest of the line is the label message.
+            let content = &text[gutter..];
+            let drawing = content
+                .iter()
+                .position(|c| !" ^-~|│╭╰─┌└┬┴╮╯'_/\\".contains(*c))
+                .unwrap_or(content.len());
+            let drawing = content[..drawing]
+                .iter()
+                .rposition(|c| *c != ' ')
+                .map_or(0, |position| position + 1);
+
+            labels.push((i + 1 + labels.len(), drawing));
+        }
+
+        let source = &lines[i];
+        let content = texts[i].len() - gutter;
+        let blank_gutter = styled::map_chars(&styled::slice(source, 0, gutter), |c| {
+            if c.is_ascii_digit() {
+                ' '
+            } else {
+                c
+            }
+        });
+        for (segment, from) in (0..content).step_by(available).enumerate() {
+            let to = (from + available).min(content);
+
+            let mut line = if segment == 0 {
+                styled::slice(source, 0, gutter)
+            } else {
+                blank_gutter.clone()
+            };
+            line.extend(styled::slice(source, gutter + from, gutter + to));
+            wrapped.push(line);
+
+            for (label, drawing) in &labels {
+                let label_line = &lines[*label];
+                // the last segment also holds underlines past the end of the source line.
+                let end = if to == content { to.max(*drawing) } else { to };
+
+                let mut line = styled::slice(label_line, 0, gutter);
+                if from < *drawing {
+                    line.extend(styled::slice(
+                        label_line,
+                        gutter + from,
+                        gutter + end.min(*drawing),
+                    ));
+                }
+
+                // keep the message next to the end of the underline.
+                if (from < *drawing && *drawing <= end) || (*drawing == 0 && segment == 0) {
+                    line.extend(styled::slice(
+                        label_line,
+                        gutter + *drawing,
+                        texts[*label].len(),
+                    ));
+                }
+
+                if styled::text(&line).chars().skip(gutter).any(|c| c != ' ') {
+                    wrapped.push(line);
+                }
+            }
+        }
+
+        i += 1 + labels.len();
+    }
+
+    wrapped
+}
+
+/// Appends the given id to the severity and code of each header of the given lines.
+fn insert_header_id(lines: &mut [StyledLine], id: &str) {
+    for line in lines {
+        let header = line.iter_mut().find(|(_, style)| {
+            matches!(
+                style.role,
+                Some(
+                    Role::HeaderBug
+                        | Role::HeaderError
+                        | Role::HeaderWarning
+                        | Role::HeaderNote
+                        | Role::HeaderHelp
+                )
+            )
+        });
+
+        if let Some((text, _)) = header {
+            text.push_str(id);
+        }
+    }
+}
+
+/// Applies the styles of the given notes to the lines rendering them.
+///
+/// Notes are rendered as plain text, each line of a note is matched by its text, after
+/// the note bullet or the indentation of the note.
+fn style_notes(lines: Vec<StyledLine>, notes: &[StyledLine], bullet: char) -> Vec<StyledLine> {
+    let notes = notes
+        .iter()
+        .flat_map(styled::split_lines)
+        .filter(|line| line.iter().any(|(_, style)| *style != Style::default()))
+        .map(|line| (styled::text(&line), line))
+        .collect::<Vec<(String, StyledLine)>>();
+    if notes.is_empty() {
+        return lines;
+    }
+
+    lines
+        .into_iter()
+        .map(|line| {
+            let text = styled::text(&line);
+            let note = notes.iter().find(|(note, _)| {
+                text.strip_suffix(note.as_str()).is_some_and(|prefix| {
+                    let prefix = prefix.trim();
+
+                    prefix.is_empty() || prefix.chars().eq([bullet])
+                })
+            });
+
+            match note {
+                Some((note, styled)) => {
+                    let column = text.chars().count() - note.chars().count();
+                    let mut line = styled::slice(&line, 0, column);
+                    line.extend(styled.iter().cloned());
+
+                    line
+                }
+                None => line,
+            }
+        })
+        .collect()
+}
+
+/// Moves the continuation lines of multi-line label messages beneath the first line of
+/// their message, codespan writes them at the start of the line, outside of the frame.
+fn attach_label_continuations(
+    lines: Vec<StyledLine>,
+    messages: &[(&str, &str)],
+) -> Vec<StyledLine> {
+    let texts = lines.iter().map(styled::text).collect::<Vec<String>>();
+
+    let mut attached = vec![];
+    let mut i = 0;
+    while i < lines.len() {
+        attached.push(lines[i].clone());
+
+        let text = &texts[i];
+        i += 1;
+
+        if frame_gutter(&text.chars().collect::<Vec<char>>()).is_none() {
+            continue;
+        }
+
+        let continued = messages.iter().find(|(first, rest)| {
+            text.ends_with(first)
+                && rest
+                    .split('\n')
+                    .enumerate()
+                    .all(|(j, line)| texts.get(i + j).is_some_and(|text| text == line))
+        });
+
+        if let Some((first, rest)) = continued {
+            // keep the pointers of the labels below, and align with the message.
+            let column = text.chars().count() - first.chars().count();
+            let prefix = styled::map_chars(&styled::slice(&lines[i - 1], 0, column), |c| {
+                if matches!(c, '|' | '│') {
+                    c
+                } else {
+                    ' '
+                }
+            });
+
+            for line in rest.split('\n') {
+                let mut continuation = prefix.clone();
+                continuation.push((
+                    line.to_string(),
+                    Style {
+                        dimmed: true,
+                        ..Style::default()
+                    },
+                ));
+
+                attached.push(continuation);
+                i += 1;
+            }
+        }
+    }
+
+    attached
+}
+
+/// A files database that shifts the line numbers of its files by a per-file offset.
+struct OffsetFiles<'f, F> {
+    files: &'f F,
+    line_offsets: FxHashMap<usize, usize>,
+}
+
+impl<'a, 'f: 'a, F: Files<'f, FileId = usize>> Files<'a> for OffsetFiles<'f, F> {
+    type FileId = usize;
+    type Name = F::Name;
+    type Source = F::Source;
+
+    fn name(&'a self, id: usize) -> Result<Self::Name, CodespanError> {
+        self.files.name(id)
+    }
+
+    fn source(&'a self, id: usize) -> Result<Self::Source, CodespanError> {
+        self.files.source(id)
+    }
+
+    fn line_index(&'a self, id: usize, byte_index: usize) -> Result<usize, CodespanError> {
+        self.files.line_index(id, byte_index)
+    }
+
+    fn line_number(&'a self, id: usize, line_index: usize) -> Result<usize, CodespanError> {
+        let line_number = self.files.line_number(id, line_index)?;
+
+        Ok(line_number + self.line_offsets.get(&id).copied().unwrap_or(0))
+    }
+
+    fn column_number(
+        &'a self,
+        id: usize,
+        line_index: usize,
+        byte_index: usize,
+    ) -> Result<usize, CodespanError> {
+        self.files.column_number(id, line_index, byte_index)
+    }
+
+    fn line_range(&'a self, id: usize, line_index: usize) -> Result<Range<usize>, CodespanError> {
+        self.files.line_range(id, line_index)
+    }
+}
+
+/// A writer that only writes whole chunks, as long as they fit within the given limit.
+struct CappedOutput<'w, T: WriteColor> {
+    writer: &'w mut T,
+    styles: &'w Styles,
+    limit: Option<usize>,
+    written: usize,
+    omitted: usize,
+}
+
+impl<'w, T: WriteColor> CappedOutput<'w, T> {
+    fn new(writer: &'w mut T, styles: &'w Styles, limit: Option<usize>) -> Self {
+        Self {
+            writer,
+            styles,
+            limit,
+            written: 0,
+            omitted: 0,
+        }
+    }
+
+    /// Render a chunk as styled lines, and write them if their text fits within the limit.
+    ///
+    /// Once a chunk has been omitted, all following chunks are omitted as well.
+    fn chunk<F>(&mut self, render: F) -> Result<(), Error>
+    where
+        F: FnOnce(&mut dyn WriteColor) -> Result<(), Error>,
+    {
+        let Some(limit) = self.limit else {
+            return render(self.writer);
+        };
+
+        let mut lines = StyledLinesWriter::new(self.styles);
+        render(&mut lines)?;
+
+        let lines = lines.into_lines();
+        let length = lines
+            .iter()
+            .map(|line| line.iter().map(|(text, _)| text.len()).sum::<usize>() + 1)
+            .sum::<usize>();
+
+        if self.omitted > 0 || self.written + length > limit {
+            self.omitted += length;
+        } else {
+            styled::write_lines(self.writer, &lines, self.styles).map_err(Error::Io)?;
+            self.written += length;
+        }
+
+        Ok(())
+    }
+
+    fn finish(self) -> Result<(), Error> {
+        if self.omitted > 0 {
+            writeln!(
+                self.writer,
+                "… output truncated ({} bytes omitted)",
+                self.omitted
+            )
+            .map_err(Error::Io)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the error of the given codespan error.
+fn codespan_error(err: CodespanError) -> Error {
+    match err {
+        CodespanError::FileMissing => Error::FileMissing,
+        CodespanError::IndexTooLarge { given, max } => Error::IndexTooLarge { given, max },
+        CodespanError::LineTooLarge { given, max } => Error::LineTooLarge { given, max },
+        CodespanError::ColumnTooLarge { given, max } => Error::ColumnTooLarge { given, max },
+        CodespanError::InvalidCharBoundary { given } => Error::InvalidCharBoundary { given },
+        CodespanError::Io(err) => Error::Io(err),
+        other => Error::CodespanError(other),
+    }
+}
+
+/// Returns the span an issue points at: its source, or else its first primary annotation,
+/// or else its first annotation.
+pub(crate) fn primary_span(issue: &Issue) -> Option<(&str, usize, usize)> {
+    match &issue.source {
+        Some((origin, from, to)) => Some((origin.as_str(), *from, *to)),
+        None => issue
+            .annotations
+            .iter()
+            .find(|annotation| annotation.r#type == AnnotationType::Primary)
+            .or_else(|| issue.annotations.first())
+            .map(|annotation| (annotation.origin.as_str(), annotation.from, annotation.to)),
+    }
+}
+
+/// Returns the error of the given JSON writing failure, failures of the writer itself are
+/// reported as `Error::Io`.
+fn json_error(err: serde_json::Error) -> Error {
+    if err.is_io() {
+        Error::Io(err.into())
+    } else {
+        Error::Serialization(err)
+    }
+}
+
+/// Returns whether the given footer should be rendered for the given counts.
+fn is_footer_shown(counts: &SummaryCounts, footer: &ReportFooter) -> bool {
+    footer.show_when_empty || counts.total > 0
+}
+
+/// Resolves the origin of annotations without one to the given default origin.
+pub(crate) fn resolve_origins<'b>(
+    issue: &'b Issue,
+    default_origin: Option<&str>,
+) -> Result<Cow<'b, Issue>, Error> {
+    if issue
+        .annotations
+        .iter()
+        .all(|annotation| !annotation.origin.is_empty())
+    {
+        return Ok(Cow::Borrowed(issue));
+    }
+
+    let default_origin = default_origin.ok_or(Error::MissingOrigin)?;
+
+    let mut issue = issue.clone();
+    for annotation in &mut issue.annotations {
+        if annotation.origin.is_empty() {
+            annotation.origin = default_origin.to_string();
+        }
+    }
+
+    Ok(Cow::Owned(issue))
+}
+
+/// Returns whether the given path matches the given glob.
+///
+/// `*` matches within a path segment, `**` matches across segments, and `?` matches a
+/// single character.
+pub(crate) fn glob_matches(glob: &str, path: &str) -> bool {
+    fn matches(glob: &[char], path: &[char]) -> bool {
+        match glob {
+            [] => path.is_empty(),
+            ['*', '*', rest @ ..] => (0..=path.len()).any(|i| matches(rest, &path[i..])),
+            ['*', rest @ ..] => (0..=path.len())
+                .take_while(|i| *i == 0 || path[i - 1] != '/')
+                .any(|i| matches(rest, &path[i..])),
+            ['?', rest @ ..] => !path.is_empty() && matches(rest, &path[1..]),
+            [c, rest @ ..] => path.first() == Some(c) && matches(rest, &path[1..]),
         }
+    }
 
-        diagnostics
+    matches(
+        &glob.chars().collect::<Vec<char>>(),
+        &path.chars().collect::<Vec<char>>(),
+    )
+}
+
+/// Returns the given note, with its code spans (text between backticks) styled.
+///
+/// Backticks are kept as-is, an unmatched backtick does not start a code span.
+fn style_note_code(note: &str, styles: &Styles) -> StyledLine {
+    let parts = note.split('`').collect::<Vec<&str>>();
+    // an odd number of backticks leaves the last one unmatched.
+    let unmatched = parts.len() % 2 == 0;
+
+    let mut styled: StyledLine = vec![];
+    for (i, part) in parts.iter().enumerate() {
+        let (text, style) = if i % 2 == 1 && !(unmatched && i == parts.len() - 1) {
+            (*part, Role::NoteCode.style(styles))
+        } else {
+            (*part, Style::default())
+        };
+
+        if i > 0 {
+            styled::push(&mut styled, "`", Style::default());
+        }
+        styled::push(&mut styled, text, style);
     }
+
+    styled
 }
@@ -0,0 +1,148 @@
+use ara_source::source::DEFAULT_NAME;
+use ara_source::SourceMap;
+
+use crate::builder::primary_span;
+use crate::builder::resolve_origins;
+use crate::builder::ReportBuilder;
+use crate::error::Error;
+use crate::issue::Issue;
+use crate::issue::IssueSeverity;
+use crate::Reportable;
+
+const CHECKSTYLE_VERSION: &str = "4.3";
+
+/// Get the report as a checkstyle XML document, rendered with a default builder.
+///
+/// This is a shorthand for `ReportBuilder::new(source_map).as_checkstyle(reportable)`, see
+/// `ReportBuilder::as_checkstyle`.
+///
+/// Example:
+///
+/// ```rust
+/// use ara_source::source::Source;
+/// use ara_source::source::SourceKind;
+/// use ara_source::SourceMap;
+///
+/// use ara_reporting::checkstyle::to_checkstyle;
+/// use ara_reporting::issue::Issue;
+/// use ara_reporting::Report;
+///
+/// let source = SourceMap::new(vec![
+///     Source::new(SourceKind::Script, "main.ara", "function main(): void {\n  $a = 1;\n}"),
+/// ]);
+///
+/// let report = Report::new()
+///     .with_issue(Issue::warning("W0001", "unused variable `$a`").with_source("main.ara", 26, 28))
+///     .with_issue(Issue::help("H0001", "expected `<`, found `&`").with_source("lib.ara", 0, 1))
+///     .with_issue(Issue::error("E0001", "...").with_source("main.ara", 9, 13))
+///     .with_issue(Issue::error("E0002", "failed to read the configuration"));
+///
+/// assert_eq!(to_checkstyle(&report, &source).unwrap(), [
+///     r#"<?xml version="1.0" encoding="UTF-8"?>"#,
+///     r#"<checkstyle version="4.3">"#,
+///     r#"  <file name="main.ara">"#,
+///     r#"    <error line="2" column="3" severity="warning" message="unused variable `$a`" source="W0001"/>"#,
+///     r#"    <error line="1" column="10" severity="error" message="..." source="E0001"/>"#,
+///     r#"  </file>"#,
+///     r#"  <file name="lib.ara">"#,
+///     r#"    <error severity="info" message="expected `&lt;`, found `&amp;`" source="H0001"/>"#,
+///     r#"  </file>"#,
+///     r#"  <file name="&lt;unknown&gt;">"#,
+///     r#"    <error severity="error" message="failed to read the configuration" source="E0002"/>"#,
+///     r#"  </file>"#,
+///     r#"</checkstyle>"#,
+///     "",
+/// ].join("\n"));
+/// ```
+pub fn to_checkstyle(reportable: &dyn Reportable, source_map: &SourceMap) -> Result<String, Error> {
+    ReportBuilder::new(source_map).as_checkstyle(reportable)
+}
+
+/// Returns the checkstyle document of the issues the given builder would render.
+pub(crate) fn checkstyle(
+    builder: &ReportBuilder,
+    reportable: &dyn Reportable,
+) -> Result<String, Error> {
+    let mut files: Vec<(String, Vec<String>)> = vec![];
+
+    for report in reportable.to_reports() {
+        let (issues, _) = builder.visible_issues(report);
+        for issue in issues {
+            let issue = resolve_origins(issue, report.default_origin.as_deref())?;
+
+            let origin = primary_span(&issue).map_or(DEFAULT_NAME, |(origin, _, _)| origin);
+            let element = error_element(builder, &issue);
+
+            match files.iter_mut().find(|(name, _)| name == origin) {
+                Some((_, elements)) => elements.push(element),
+                None => files.push((origin.to_string(), vec![element])),
+            }
+        }
+    }
+
+    let mut output = String::new();
+    output.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    output.push_str(&format!("<checkstyle version=\"{CHECKSTYLE_VERSION}\">\n"));
+    for (name, elements) in files {
+        output.push_str(&format!("  <file name=\"{}\">\n", escape(&name)));
+        for element in elements {
+            output.push_str(&format!("    {element}\n"));
+        }
+        output.push_str("  </file>\n");
+    }
+    output.push_str("</checkstyle>\n");
+
+    Ok(output)
+}
+
+/// Returns the `<error>` element of the given issue.
+fn error_element(builder: &ReportBuilder, issue: &Issue) -> String {
+    let mut attributes = vec![];
+
+    if let Some((line, column)) =
+        primary_span(issue).and_then(|(origin, from, _)| builder.locate(origin, from))
+    {
+        attributes.push(format!("line=\"{line}\""));
+        attributes.push(format!("column=\"{column}\""));
+    }
+
+    attributes.push(format!(
+        "severity=\"{}\"",
+        match issue.severity {
+            IssueSeverity::Bug | IssueSeverity::Error => "error",
+            IssueSeverity::Warning => "warning",
+            IssueSeverity::Note | IssueSeverity::Help => "info",
+        }
+    ));
+    attributes.push(format!("message=\"{}\"", escape(&issue.message)));
+
+    if let Some(code) = &issue.code {
+        attributes.push(format!("source=\"{}\"", escape(code)));
+    }
+
+    format!("<error {}/>", attributes.join(" "))
+}
+
+/// Escapes the given text for use in an XML attribute value.
+///
+/// Characters that are not allowed in XML 1.0 documents, such as the escape character of
+/// ANSI sequences, are removed.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\n' => escaped.push_str("&#10;"),
+            '\r' => escaped.push_str("&#13;"),
+            '\t' => escaped.push_str("&#9;"),
+            '\u{0}'..='\u{1f}' | '\u{fffe}' | '\u{ffff}' => {}
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
@@ -7,14 +7,65 @@ pub enum Error {
     FileMissing,
     /// The file is present, but does not contain the specified byte index.
     IndexTooLarge { given: usize, max: usize },
+    /// The span ends before it starts.
+    InvertedSpan { from: usize, to: usize },
     /// The file is present, but does not contain the specified line index.
     LineTooLarge { given: usize, max: usize },
     /// The file is present and contains the specified line index, but the line does not contain the specified column index.
     ColumnTooLarge { given: usize, max: usize },
     /// The given index is contained in the file, but is not a boundary of a UTF-8 code point.
     InvalidCharBoundary { given: usize },
+    /// The source does not contain the specified match index, it only has `max` matches.
+    MatchTooLarge { given: usize, max: usize },
+    /// The given index of the named source is not a boundary of a UTF-8 code point.
+    InvalidSourceEncoding { origin: String, given: usize },
+    /// An annotation has no origin, and the report has no default origin.
+    MissingOrigin,
+    /// The schema version of serialized reports is not the version of this crate.
+    SchemaVersionMismatch { expected: String, given: String },
+    /// The given string is not the name of a severity, one of `note`, `help`, `warning`,
+    /// `error`, or `bug`.
+    UnknownSeverity { given: String },
     /// There was a error while doing IO.
     Io(std::io::Error),
+    /// There was a error while writing to a `fmt::Write` target.
+    Fmt(std::fmt::Error),
+    /// There was a error while serializing or deserializing JSON.
+    Serialization(serde_json::Error),
     /// Codespan error.
     CodespanError(CodespanError),
 }
+
+/// The span of an issue that failed validation.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum SpanKind {
+    /// The source of the issue.
+    Source,
+    /// The annotation at the given index of the issue.
+    Annotation(usize),
+}
+
+/// The reason a span failed validation.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum SpanFailure {
+    /// The span has no origin, and the report has no default origin.
+    MissingOrigin,
+    /// The origin of the span is not in the source map.
+    SourceMissing,
+    /// The given offset is past the end of the source content.
+    OutOfBounds { given: usize, max: usize },
+    /// The span ends before it starts.
+    Inverted { from: usize, to: usize },
+    /// The given offset is not a boundary of a UTF-8 code point.
+    InvalidCharBoundary { given: usize },
+}
+
+/// A span of a report that can not be rendered against a source map.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SpanError {
+    /// The index of the issue in the report.
+    pub issue: usize,
+    pub kind: SpanKind,
+    pub origin: String,
+    pub failure: SpanFailure,
+}
@@ -0,0 +1,152 @@
+use ara_source::source::DEFAULT_NAME;
+use ara_source::SourceMap;
+
+use crate::builder::primary_span;
+use crate::builder::resolve_origins;
+use crate::builder::ReportBuilder;
+use crate::error::Error;
+use crate::issue::Issue;
+use crate::issue::IssueSeverity;
+use crate::Reportable;
+
+/// Get the report as GitHub Actions workflow commands, rendered with a default builder.
+///
+/// This is a shorthand for `ReportBuilder::new(source_map).as_workflow_commands(reportable)`,
+/// see `ReportBuilder::as_workflow_commands`.
+///
+/// Example:
+///
+/// ```rust
+/// use ara_source::source::Source;
+/// use ara_source::source::SourceKind;
+/// use ara_source::SourceMap;
+///
+/// use ara_reporting::github::to_workflow_commands;
+/// use ara_reporting::issue::Issue;
+/// use ara_reporting::Report;
+///
+/// let source = SourceMap::new(vec![
+///     Source::new(SourceKind::Script, "main.ara", "function main(): void {\n  $a = 1;\n}"),
+/// ]);
+///
+/// let report = Report::new()
+///     .with_issue(Issue::warning("W0001", "unused variable `$a`").with_source("main.ara", 26, 28))
+///     .with_issue(Issue::bug("B0001", "100% broken\nplease report").with_source("lib.ara", 0, 1))
+///     .with_issue(Issue::help("H0001", "..."));
+///
+/// assert_eq!(to_workflow_commands(&report, &source).unwrap(), [
+///     "::warning file=main.ara,line=2,col=3,endLine=2,endColumn=5,title=W0001::unused variable `$a`",
+///     "::error file=lib.ara,title=B0001::100%25 broken%0Aplease report",
+///     "::notice title=H0001::...",
+///     "",
+/// ].join("\n"));
+/// ```
+pub fn to_workflow_commands(
+    reportable: &dyn Reportable,
+    source_map: &SourceMap,
+) -> Result<String, Error> {
+    ReportBuilder::new(source_map).as_workflow_commands(reportable)
+}
+
+/// Returns the workflow commands of the issues the given builder would render.
+pub(crate) fn workflow_commands(
+    builder: &ReportBuilder,
+    reportable: &dyn Reportable,
+) -> Result<String, Error> {
+    let mut output = String::new();
+
+    for report in reportable.to_reports() {
+        let (issues, _) = builder.visible_issues(report);
+        let issues = issues
+            .into_iter()
+            .map(|issue| resolve_origins(issue, report.default_origin.as_deref()))
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        if !builder.fold_groups {
+            for issue in &issues {
+                output.push_str(&command(builder, issue));
+                output.push('\n');
+            }
+
+            continue;
+        }
+
+        let mut groups: Vec<(Option<&str>, Vec<&Issue>)> = vec![];
+        for issue in &issues {
+            match groups
+                .iter_mut()
+                .find(|(origin, _)| *origin == issue.origin())
+            {
+                Some((_, group)) => group.push(issue),
+                None => groups.push((issue.origin(), vec![issue])),
+            }
+        }
+
+        for (origin, issues) in groups {
+            output.push_str(&format!(
+                "::group::{}\n",
+                escape_data(origin.unwrap_or(DEFAULT_NAME))
+            ));
+
+            for issue in issues {
+                output.push_str(&command(builder, issue));
+                output.push('\n');
+            }
+
+            output.push_str("::endgroup::\n");
+        }
+    }
+
+    Ok(output)
+}
+
+/// Returns the workflow command of the given issue.
+fn command(builder: &ReportBuilder, issue: &Issue) -> String {
+    let mut properties = vec![];
+
+    if let Some((origin, from, to)) = primary_span(issue) {
+        properties.push(format!("file={}", escape_property(origin)));
+
+        if let (Some((line, column)), Some((end_line, end_column))) =
+            (builder.locate(origin, from), builder.locate(origin, to))
+        {
+            properties.push(format!("line={line}"));
+            properties.push(format!("col={column}"));
+            properties.push(format!("endLine={end_line}"));
+            properties.push(format!("endColumn={end_column}"));
+        }
+    }
+
+    if let Some(code) = &issue.code {
+        properties.push(format!("title={}", escape_property(code)));
+    }
+
+    let command = match issue.severity {
+        IssueSeverity::Bug | IssueSeverity::Error => "error",
+        IssueSeverity::Warning => "warning",
+        IssueSeverity::Note | IssueSeverity::Help => "notice",
+    };
+
+    if properties.is_empty() {
+        format!("::{}::{}", command, escape_data(&issue.message))
+    } else {
+        format!(
+            "::{} {}::{}",
+            command,
+            properties.join(","),
+            escape_data(&issue.message)
+        )
+    }
+}
+
+/// Escapes the message of a workflow command.
+pub(crate) fn escape_data(data: &str) -> String {
+    data.replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// Escapes a property value of a workflow command.
+fn escape_property(value: &str) -> String {
+    escape_data(value).replace(':', "%3A").replace(',', "%2C")
+}
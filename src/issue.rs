@@ -1,9 +1,18 @@
 use codespan_reporting::diagnostic::Severity;
 use schemars::JsonSchema;
 use serde::Deserialize;
+use serde::Deserializer;
 use serde::Serialize;
 
+use ara_source::SourceMap;
+
 use crate::annotation::Annotation;
+use crate::annotation::AnnotationType;
+use crate::error::Error;
+use crate::line_column;
+use crate::range_contains;
+use crate::ranges_overlap;
+use crate::source_content;
 
 #[derive(
     Debug, PartialEq, Eq, Ord, Copy, Clone, Hash, PartialOrd, Deserialize, Serialize, JsonSchema,
@@ -21,11 +30,20 @@ pub enum IssueSeverity {
 #[serde(rename_all = "snake_case")]
 pub struct Issue {
     pub severity: IssueSeverity,
+    #[serde(default, deserialize_with = "deserialize_code")]
     pub code: Option<String>,
     pub message: String,
     pub source: Option<(String, usize, usize)>,
     pub annotations: Vec<Annotation>,
     pub notes: Vec<String>,
+    #[serde(default)]
+    pub hidden: bool,
+    #[serde(default)]
+    pub id: Option<u64>,
+    #[serde(default)]
+    pub weight: i32,
+    #[serde(default = "default_include_in_summary")]
+    pub include_in_summary: bool,
 }
 
 /// A report issue.
@@ -63,6 +81,31 @@ pub struct Issue {
 /// ```
 impl Issue {
     /// Create a new issue with the given code and message.
+    ///
+    /// The message may span multiple lines, the first line is rendered as the header, and
+    /// the following ones are rendered as notes.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// use ara_reporting::issue::Issue;
+    /// use ara_reporting::issue::IssueSeverity;
+    /// # use ara_source::SourceMap;
+    /// # use ara_reporting::builder::ColorChoice;
+    /// # use ara_reporting::builder::ReportBuilder;
+    /// # use ara_reporting::Report;
+    ///
+    /// let issue = Issue::new(IssueSeverity::Error, "failed to parse `main.ara`\nunexpected end of file")
+    ///     .with_note("check for unclosed braces");
+    ///
+    /// # let source_map = SourceMap::new(vec![]);
+    /// let output = ReportBuilder::new(&source_map)
+    ///     .with_colors(ColorChoice::Never)
+    ///     .as_string(&Report::from(issue))
+    ///     .unwrap();
+    ///
+    /// assert_eq!(output, "error: failed to parse `main.ara`\n = unexpected end of file\n = check for unclosed braces\n\n");
+    /// ```
     pub fn new<M: Into<String>>(severity: IssueSeverity, message: M) -> Self {
         Self {
             severity,
@@ -71,6 +114,10 @@ impl Issue {
             source: None,
             annotations: Vec::new(),
             notes: Vec::new(),
+            hidden: false,
+            id: None,
+            weight: 0,
+            include_in_summary: true,
         }
     }
 
@@ -176,15 +223,129 @@ impl Issue {
         Self::new(IssueSeverity::Error, message)
     }
 
+    /// Create a new `Issue` with the given severity from anything that derives `std::error::Error`.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// use ara_reporting::issue::Issue;
+    /// use ara_reporting::issue::IssueSeverity;
+    ///
+    /// let error: std::num::ParseIntError = "NaN".parse::<u8>().unwrap_err();
+    /// let issue = Issue::from_error_with(error, IssueSeverity::Warning);
+    ///
+    /// assert_eq!(issue.severity, IssueSeverity::Warning);
+    /// assert_eq!("invalid digit found in string", issue.message);
+    /// ```
+    pub fn from_error_with<E: std::error::Error>(error: E, severity: IssueSeverity) -> Self {
+        Self::new(severity, error.to_string())
+    }
+
+    /// Set the severity of this issue.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// use ara_reporting::issue::Issue;
+    /// use ara_reporting::issue::IssueSeverity;
+    ///
+    /// let issue = Issue::warning("0003", "...")
+    ///     .with_severity(IssueSeverity::Error);
+    ///
+    /// assert_eq!(issue.severity, IssueSeverity::Error);
+    /// assert_eq!(issue.code, Some("0003".to_string()));
+    /// ```
+    #[must_use]
+    pub fn with_severity(mut self, severity: IssueSeverity) -> Self {
+        self.severity = severity;
+
+        self
+    }
+
     /// Add a code to this issue.
+    ///
+    /// Empty, or whitespace-only codes are ignored.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// use ara_reporting::issue::Issue;
+    /// use ara_reporting::issue::IssueSeverity;
+    /// # use ara_source::SourceMap;
+    /// # use ara_reporting::builder::ReportBuilder;
+    /// # use ara_reporting::builder::ColorChoice;
+    /// # use ara_reporting::Report;
+    ///
+    /// let issue = Issue::new(IssueSeverity::Error, "...").with_code("E0001");
+    /// assert_eq!(issue.code, Some("E0001".to_string()));
+    ///
+    /// let issue = Issue::new(IssueSeverity::Error, "...").with_code("");
+    /// assert_eq!(issue.code, None);
+    /// assert_eq!(issue.to_string(), "error: ...");
+    ///
+    /// let issue = Issue::error("  ", "...");
+    /// assert_eq!(issue.code, None);
+    /// assert_eq!(issue.to_string(), "error: ...");
+    ///
+    /// let issue: Issue = serde_json::from_str(
+    ///     r#"{"severity":{"type":"error"},"code":" ","message":"...","source":null,"annotations":[],"notes":[]}"#,
+    /// ).unwrap();
+    /// assert_eq!(issue.code, None);
+    ///
+    /// # let source_map = SourceMap::new(vec![]);
+    /// let output = ReportBuilder::new(&source_map)
+    ///     .with_colors(ColorChoice::Never)
+    ///     .as_string(&Report::from(issue))
+    ///     .unwrap();
+    /// assert!(output.starts_with("error: ..."));
+    /// ```
     #[must_use]
     pub fn with_code<C: Into<String>>(mut self, code: C) -> Self {
-        self.code = Some(code.into());
+        let code = code.into();
+        self.code = if code.trim().is_empty() {
+            None
+        } else {
+            Some(code)
+        };
 
         self
     }
 
     /// Add an annotation to this issue.
+    ///
+    /// An issue without a source, and without a primary annotation, is anchored on its
+    /// first annotation.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// # use ara_source::source::Source;
+    /// # use ara_source::source::SourceKind;
+    /// # use ara_source::SourceMap;
+    /// # use ara_reporting::annotation::Annotation;
+    /// # use ara_reporting::builder::ColorChoice;
+    /// # use ara_reporting::builder::ReportBuilder;
+    /// # use ara_reporting::issue::Issue;
+    /// # use ara_reporting::Report;
+    /// let source = SourceMap::new(vec![
+    ///     Source::new(SourceKind::Script, "main.ara", "function main(): void {\n    $a = 1;\n}"),
+    /// ]);
+    ///
+    /// let report = Report::new().with_issue(
+    ///     Issue::error("E0001", "unused variable")
+    ///         .with_annotation(Annotation::secondary("main.ara", 28, 30).with_message("assigned here"))
+    ///         .with_annotation(Annotation::secondary("main.ara", 9, 13).with_message("in this function")),
+    /// );
+    ///
+    /// let output = ReportBuilder::new(&source)
+    ///     .with_colors(ColorChoice::Never)
+    ///     .as_string(&report)
+    ///     .unwrap();
+    ///
+    /// assert!(output.contains("--> main.ara:2:5"));
+    /// assert!(output.contains("^^ assigned here"));
+    /// assert!(output.contains("---- in this function"));
+    /// ```
     #[must_use]
     pub fn with_annotation(mut self, annotation: Annotation) -> Self {
         self.annotations.push(annotation);
@@ -192,6 +353,173 @@ impl Issue {
         self
     }
 
+    /// Add the annotation built by the given function to this issue, if the condition holds.
+    ///
+    /// The function is only called when the condition holds.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// use ara_reporting::annotation::Annotation;
+    /// use ara_reporting::issue::Issue;
+    ///
+    /// let definition: Option<(usize, usize)> = None;
+    ///
+    /// let issue = Issue::error("E0001", "undefined variable `$a`")
+    ///     .with_annotation_if(true, || Annotation::primary("main.ara", 26, 28))
+    ///     .with_annotation_if(definition.is_some(), || {
+    ///         let (from, to) = definition.unwrap();
+    ///
+    ///         Annotation::secondary("main.ara", from, to)
+    ///     });
+    ///
+    /// assert_eq!(issue.annotations, vec![Annotation::primary("main.ara", 26, 28)]);
+    /// ```
+    #[must_use]
+    pub fn with_annotation_if<F: FnOnce() -> Annotation>(self, condition: bool, f: F) -> Self {
+        if condition {
+            self.with_annotation(f())
+        } else {
+            self
+        }
+    }
+
+    /// Add a group of annotations to this issue.
+    ///
+    /// Annotations of a group that are close together are rendered in one continuous
+    /// frame, showing the lines between them, while annotations that are far apart are
+    /// still separated by a break.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// # use ara_source::source::Source;
+    /// # use ara_source::source::SourceKind;
+    /// # use ara_source::SourceMap;
+    /// # use ara_reporting::annotation::Annotation;
+    /// # use ara_reporting::builder::ColorChoice;
+    /// # use ara_reporting::builder::ReportBuilder;
+    /// # use ara_reporting::issue::Issue;
+    /// # use ara_reporting::Report;
+    /// let source = SourceMap::new(vec![Source::new(
+    ///     SourceKind::Script,
+    ///     "main.ara",
+    ///     "function main(): void {\n  $a = 1;\n  $b = 2;\n  $c = 3;\n  $d = 4;\n  $e = $a;\n}",
+    /// )]);
+    ///
+    /// let issue = Issue::error("E0001", "...").with_annotation_group(vec![
+    ///     Annotation::secondary("main.ara", 26, 28).with_message("defined here"),
+    ///     Annotation::secondary("main.ara", 71, 73).with_message("used here"),
+    /// ]);
+    ///
+    /// assert_eq!(issue.annotations[0].group, Some(0));
+    /// assert_eq!(issue.annotations[1].group, Some(0));
+    ///
+    /// let output = ReportBuilder::new(&source)
+    ///     .with_colors(ColorChoice::Never)
+    ///     .as_string(&Report::from(issue))
+    ///     .unwrap();
+    ///
+    /// assert!(output.contains("3 |   $b = 2;\n4 |   $c = 3;\n5 |   $d = 4;\n"));
+    /// ```
+    #[must_use]
+    pub fn with_annotation_group(mut self, annotations: Vec<Annotation>) -> Self {
+        let group = self
+            .annotations
+            .iter()
+            .filter_map(|annotation| annotation.group)
+            .max()
+            .map_or(0, |group| group + 1);
+
+        self.annotations
+            .extend(annotations.into_iter().map(|mut annotation| {
+                annotation.group = Some(group);
+
+                annotation
+            }));
+
+        self
+    }
+
+    /// Returns the origin of this issue.
+    ///
+    /// The origin of an issue is the origin of its source, or the origin of its first
+    /// primary annotation.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// use ara_reporting::annotation::Annotation;
+    /// use ara_reporting::issue::Issue;
+    ///
+    /// let issue = Issue::error("0003", "...").with_source("main.ara", 10, 11);
+    /// assert_eq!(issue.origin(), Some("main.ara"));
+    ///
+    /// let issue = Issue::error("0003", "...")
+    ///     .with_annotation(Annotation::secondary("lib.ara", 1, 2))
+    ///     .with_annotation(Annotation::primary("main.ara", 10, 11));
+    /// assert_eq!(issue.origin(), Some("main.ara"));
+    ///
+    /// let issue = Issue::error("0003", "...");
+    /// assert_eq!(issue.origin(), None);
+    /// ```
+    pub fn origin(&self) -> Option<&str> {
+        match &self.source {
+            Some((origin, _, _)) => Some(origin),
+            None => self
+                .annotations
+                .iter()
+                .find(|annotation| annotation.r#type == AnnotationType::Primary)
+                .map(|annotation| annotation.origin.as_str()),
+        }
+    }
+
+    /// Remove annotations pointing at the same span as a previous annotation.
+    ///
+    /// The first annotation of each span is kept, and the messages of the removed
+    /// annotations are appended to its message.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// use ara_reporting::annotation::Annotation;
+    /// use ara_reporting::issue::Issue;
+    ///
+    /// let mut issue = Issue::error("0003", "...")
+    ///     .with_annotation(Annotation::secondary("main.ara", 10, 12).with_message("this is `int`"))
+    ///     .with_annotation(Annotation::secondary("main.ara", 14, 16))
+    ///     .with_annotation(Annotation::secondary("main.ara", 10, 12).with_message("found here"))
+    ///     .with_annotation(Annotation::secondary("main.ara", 14, 16).with_message("and here"));
+    ///
+    /// issue.dedup_annotations();
+    ///
+    /// assert_eq!(issue.annotations, vec![
+    ///     Annotation::secondary("main.ara", 10, 12).with_message("this is `int`; found here"),
+    ///     Annotation::secondary("main.ara", 14, 16).with_message("and here"),
+    /// ]);
+    /// ```
+    pub fn dedup_annotations(&mut self) {
+        let mut annotations: Vec<Annotation> = Vec::with_capacity(self.annotations.len());
+        for annotation in self.annotations.drain(..) {
+            match annotations
+                .iter_mut()
+                .find(|existing| existing.same_span(&annotation))
+            {
+                Some(existing) => {
+                    if let Some(message) = annotation.message {
+                        existing.message = Some(match existing.message.take() {
+                            Some(existing) => format!("{existing}; {message}"),
+                            None => message,
+                        });
+                    }
+                }
+                None => annotations.push(annotation),
+            }
+        }
+
+        self.annotations = annotations;
+    }
+
     /// Add a note to this issue.
     #[must_use]
     pub fn with_note<S: Into<String>>(mut self, note: S) -> Self {
@@ -200,6 +528,205 @@ impl Issue {
         self
     }
 
+    /// Add the note built by the given function to this issue, if the condition holds.
+    ///
+    /// The function is only called when the condition holds.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// use ara_reporting::issue::Issue;
+    ///
+    /// let issue = Issue::error("E0001", "undefined variable `$a`")
+    ///     .with_note_if(true, || "variables must be defined before use")
+    ///     .with_note_if(false, || "never added");
+    ///
+    /// assert_eq!(issue.notes, vec!["variables must be defined before use".to_string()]);
+    /// ```
+    #[must_use]
+    pub fn with_note_if<S: Into<String>, F: FnOnce() -> S>(self, condition: bool, f: F) -> Self {
+        if condition {
+            self.with_note(f())
+        } else {
+            self
+        }
+    }
+
+    /// Hide this issue from the rendered output.
+    ///
+    /// Hidden issues are kept in the report data, and are still counted in the footer summary.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// use ara_reporting::issue::Issue;
+    ///
+    /// let issue = Issue::error("0003", "...");
+    /// assert!(!issue.hidden);
+    ///
+    /// let issue = issue.hidden();
+    /// assert!(issue.hidden);
+    ///
+    /// # use ara_source::SourceMap;
+    /// # use ara_reporting::builder::ReportBuilder;
+    /// # use ara_reporting::builder::ColorChoice;
+    /// # use ara_reporting::Report;
+    /// # use ara_reporting::ReportFooter;
+    /// let report = Report::new()
+    ///     .with_issue(issue)
+    ///     .with_issue(Issue::warning("0004", "visible warning"))
+    ///     .with_footer(ReportFooter::new("done"));
+    ///
+    /// let source_map = SourceMap::new(vec![]);
+    /// let output = ReportBuilder::new(&source_map)
+    ///     .with_colors(ColorChoice::Never)
+    ///     .as_string(&report)
+    ///     .unwrap();
+    ///
+    /// assert!(!output.contains("error[0003]"));
+    /// assert!(output.contains("warning[0004]: visible warning"));
+    /// assert!(output.contains("summary: 1 warning(s), 1 error(s), 1 hidden"));
+    /// ```
+    #[must_use]
+    pub fn hidden(mut self) -> Self {
+        self.hidden = true;
+
+        self
+    }
+
+    /// Set whether this issue is counted in the summary of the report footer.
+    ///
+    /// Issues excluded from the summary are still rendered.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// # use ara_source::SourceMap;
+    /// # use ara_reporting::builder::ColorChoice;
+    /// # use ara_reporting::builder::ReportBuilder;
+    /// # use ara_reporting::issue::Issue;
+    /// # use ara_reporting::Report;
+    /// # use ara_reporting::ReportFooter;
+    /// let report = Report::new()
+    ///     .with_issue(Issue::error("E0001", "..."))
+    ///     .with_issue(Issue::note("N0001", "analysis completed").with_include_in_summary(false))
+    ///     .with_footer(ReportFooter::new("done"));
+    ///
+    /// assert_eq!(report.summary_line(), "1 error(s)");
+    ///
+    /// # let source = SourceMap::new(vec![]);
+    /// let output = ReportBuilder::new(&source)
+    ///     .with_colors(ColorChoice::Never)
+    ///     .as_string(&report)
+    ///     .unwrap();
+    ///
+    /// assert!(output.contains("note[N0001]: analysis completed"));
+    /// assert!(output.contains("summary: 1 error(s)\n"));
+    /// ```
+    #[must_use]
+    pub fn with_include_in_summary(mut self, enabled: bool) -> Self {
+        self.include_in_summary = enabled;
+
+        self
+    }
+
+    /// Set the weight of this issue.
+    ///
+    /// When selecting the top issues of a report, issues of the same severity are ordered
+    /// from the highest to the lowest weight.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// # use ara_source::SourceMap;
+    /// # use ara_reporting::builder::ColorChoice;
+    /// # use ara_reporting::builder::ReportBuilder;
+    /// # use ara_reporting::issue::Issue;
+    /// # use ara_reporting::Report;
+    /// let report = Report::new()
+    ///     .with_issue(Issue::error("E0001", "first error"))
+    ///     .with_issue(Issue::warning("W0001", "heavy warning").with_weight(100))
+    ///     .with_issue(Issue::error("E0002", "actionable error").with_weight(10));
+    ///
+    /// # let source = SourceMap::new(vec![]);
+    /// let output = ReportBuilder::new(&source)
+    ///     .with_colors(ColorChoice::Never)
+    ///     .with_top(Some(1))
+    ///     .as_string(&report)
+    ///     .unwrap();
+    ///
+    /// assert!(output.starts_with("error[E0002]: actionable error"));
+    /// assert!(!output.contains("first error"));
+    /// assert!(!output.contains("heavy warning"));
+    /// ```
+    #[must_use]
+    pub fn with_weight(mut self, weight: i32) -> Self {
+        self.weight = weight;
+
+        self
+    }
+
+    /// Add a "did you mean" note suggesting the candidate closest to the typed text.
+    ///
+    /// A candidate is suggested when its edit distance to the typed text is at most a third of
+    /// the typed text length (at least 1), see `Issue::with_suggestions_from_within` to use
+    /// a custom threshold.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// use ara_reporting::issue::Issue;
+    ///
+    /// let issue = Issue::error("E0001", "undefined variable `$valeu`")
+    ///     .with_suggestions_from(&["$value", "$values", "$other"], "$valeu");
+    /// assert_eq!(issue.notes, vec!["help: did you mean `$value`?".to_string()]);
+    ///
+    /// let issue = Issue::error("E0001", "undefined variable `$foo`")
+    ///     .with_suggestions_from(&["$value", "$bar"], "$foo");
+    /// assert!(issue.notes.is_empty());
+    /// ```
+    #[must_use]
+    pub fn with_suggestions_from(self, candidates: &[&str], typed: &str) -> Self {
+        let threshold = std::cmp::max(1, typed.chars().count() / 3);
+
+        self.with_suggestions_from_within(candidates, typed, threshold)
+    }
+
+    /// Add a "did you mean" note suggesting the candidate closest to the typed text,
+    /// if its edit distance is within the given threshold.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// use ara_reporting::issue::Issue;
+    ///
+    /// let issue = Issue::error("E0001", "undefined function `lenght`")
+    ///     .with_suggestions_from_within(&["length", "strlen"], "lenght", 1);
+    /// assert!(issue.notes.is_empty());
+    ///
+    /// let issue = Issue::error("E0001", "undefined function `lenght`")
+    ///     .with_suggestions_from_within(&["length", "strlen"], "lenght", 2);
+    /// assert_eq!(issue.notes, vec!["help: did you mean `length`?".to_string()]);
+    /// ```
+    #[must_use]
+    pub fn with_suggestions_from_within(
+        self,
+        candidates: &[&str],
+        typed: &str,
+        threshold: usize,
+    ) -> Self {
+        let suggestion = candidates
+            .iter()
+            .map(|candidate| (candidate, levenshtein(candidate, typed)))
+            .filter(|(_, distance)| *distance > 0 && *distance <= threshold)
+            .min_by_key(|(_, distance)| *distance);
+
+        match suggestion {
+            Some((candidate, _)) => self.with_note(format!("help: did you mean `{candidate}`?")),
+            None => self,
+        }
+    }
+
     /// Add a source/position details to this issue.
     #[must_use]
     pub fn with_source<O: Into<String>>(mut self, source: O, from: usize, to: usize) -> Self {
@@ -207,10 +734,236 @@ impl Issue {
 
         self
     }
+
+    /// Returns whether the source of this issue overlaps the source of the given issue.
+    ///
+    /// Sources are half-open like `Issue::contains_byte`, so sources overlap when they share
+    /// at least one byte, and an empty source overlaps nothing. Issues without a source
+    /// never overlap.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// use ara_reporting::issue::Issue;
+    ///
+    /// let issue = Issue::error("E0001", "...").with_source("main.ara", 10, 14);
+    ///
+    /// assert!(issue.overlaps(&Issue::warning("W0001", "...").with_source("main.ara", 12, 20)));
+    /// assert!(!issue.overlaps(&Issue::warning("W0001", "...").with_source("main.ara", 13, 13)));
+    /// assert!(!issue.overlaps(&Issue::warning("W0001", "...").with_source("main.ara", 14, 20)));
+    /// assert!(!issue.overlaps(&Issue::warning("W0001", "...").with_source("lib.ara", 10, 14)));
+    /// assert!(!issue.overlaps(&Issue::warning("W0001", "...")));
+    /// ```
+    pub fn overlaps(&self, other: &Issue) -> bool {
+        match (&self.source, &other.source) {
+            (Some((origin, from, to)), Some((other_origin, other_from, other_to))) => {
+                origin == other_origin && ranges_overlap((*from, *to), (*other_from, *other_to))
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns whether the source of this issue contains the given byte of the given origin.
+    ///
+    /// This is useful to find the issues under a cursor. The source is half-open like
+    /// `Annotation::contains`, it contains `byte` when `from <= byte < to`, so an empty
+    /// source contains no byte.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// use ara_reporting::issue::Issue;
+    ///
+    /// let issue = Issue::error("E0001", "...").with_source("main.ara", 10, 14);
+    ///
+    /// assert!(issue.contains_byte("main.ara", 10));
+    /// assert!(issue.contains_byte("main.ara", 13));
+    /// assert!(!issue.contains_byte("main.ara", 14));
+    /// assert!(!issue.contains_byte("lib.ara", 10));
+    ///
+    /// let issue = Issue::error("E0001", "...").with_source("main.ara", 10, 10);
+    /// assert!(!issue.contains_byte("main.ara", 10));
+    /// ```
+    pub fn contains_byte(&self, origin: &str, byte: usize) -> bool {
+        match &self.source {
+            Some((source, from, to)) => source == origin && range_contains((*from, *to), byte),
+            None => false,
+        }
+    }
+
+    /// Returns the fingerprint of this issue.
+    ///
+    /// The fingerprint is a hash of the severity, code, message, and source of the issue,
+    /// it is stable across runs, and across versions of the compiler.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// use ara_reporting::issue::Issue;
+    ///
+    /// let issue = Issue::error("E0417", "`match` arms have incompatible types")
+    ///     .with_source("main.ara", 10, 14);
+    ///
+    /// assert_eq!(issue.fingerprint(), 0xc1fb_7b16_53cb_22b1);
+    /// assert_eq!(issue.fingerprint(), issue.clone().with_note("...").fingerprint());
+    /// assert_ne!(issue.fingerprint(), issue.clone().with_source("main.ara", 11, 14).fingerprint());
+    /// ```
+    pub fn fingerprint(&self) -> u64 {
+        let source = self
+            .source
+            .as_ref()
+            .map(|(origin, from, to)| format!("{origin}\0{from}\0{to}"))
+            .unwrap_or_default();
+
+        let fingerprint = format!(
+            "{}\0{}\0{}\0{}",
+            self.severity,
+            self.code.as_deref().unwrap_or_default(),
+            self.message,
+            source
+        );
+
+        // FNV-1a, unlike `std::hash`, its output is guaranteed to never change.
+        fingerprint
+            .bytes()
+            .fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+                (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+            })
+    }
+
+    /// Returns the location of the source of this issue as `origin:line:column`.
+    ///
+    /// Returns `None` if the issue has no source, or if its origin or offset can not be
+    /// found in the given source map.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// use ara_source::source::Source;
+    /// use ara_source::source::SourceKind;
+    /// use ara_source::SourceMap;
+    ///
+    /// use ara_reporting::issue::Issue;
+    ///
+    /// let source = SourceMap::new(vec![
+    ///     Source::new(SourceKind::Script, "main.ara", "function main(): void {\n    $a = 1;\n}"),
+    /// ]);
+    ///
+    /// let issue = Issue::error("E0001", "...").with_source("main.ara", 28, 30);
+    /// assert_eq!(issue.location_string(&source), Some("main.ara:2:5".to_string()));
+    ///
+    /// assert_eq!(Issue::error("E0001", "...").location_string(&source), None);
+    /// assert_eq!(
+    ///     Issue::error("E0001", "...").with_source("lib.ara", 0, 1).location_string(&source),
+    ///     None
+    /// );
+    /// ```
+    pub fn location_string(&self, source_map: &SourceMap) -> Option<String> {
+        let (origin, from, _) = self.source.as_ref()?;
+        let (line, column) = line_column(source_content(source_map, origin)?, *from).ok()?;
+
+        Some(format!("{origin}:{line}:{column}"))
+    }
+
+    /// Returns the 1-based line and column of the start and end of the source of this issue.
+    ///
+    /// Columns are counted in characters, not bytes.
+    ///
+    /// Returns `None` if the issue has no source, or if its origin or offsets can not be
+    /// found in the given source map.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// use ara_source::source::Source;
+    /// use ara_source::source::SourceKind;
+    /// use ara_source::SourceMap;
+    ///
+    /// use ara_reporting::issue::Issue;
+    ///
+    /// let source = SourceMap::new(vec![
+    ///     Source::new(SourceKind::Script, "main.ara", "function main(): void {\n    $é = \"ü\";\n}"),
+    /// ]);
+    ///
+    /// let issue = Issue::error("E0001", "...").with_source("main.ara", 28, 39);
+    /// assert_eq!(issue.line_column(&source), Some(((2, 5), (2, 14))));
+    ///
+    /// let issue = Issue::error("E0001", "...").with_source("main.ara", 28, 40);
+    /// assert_eq!(issue.line_column(&source), Some(((2, 5), (3, 1))));
+    ///
+    /// assert_eq!(Issue::error("E0001", "...").line_column(&source), None);
+    /// assert_eq!(
+    ///     Issue::error("E0001", "...").with_source("lib.ara", 0, 1).line_column(&source),
+    ///     None
+    /// );
+    /// ```
+    pub fn line_column(&self, source_map: &SourceMap) -> Option<((usize, usize), (usize, usize))> {
+        let (origin, from, to) = self.source.as_ref()?;
+        let content = source_content(source_map, origin)?;
+
+        Some((
+            line_column(content, *from).ok()?,
+            line_column(content, *to).ok()?,
+        ))
+    }
+
+    /// Returns the location of the source of this issue as `origin@from:to`, in bytes.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// use ara_reporting::issue::Issue;
+    ///
+    /// let issue = Issue::error("E0001", "...").with_source("main.ara", 28, 30);
+    /// assert_eq!(issue.byte_location_string(), Some("main.ara@28:30".to_string()));
+    ///
+    /// assert_eq!(Issue::error("E0001", "...").byte_location_string(), None);
+    /// ```
+    pub fn byte_location_string(&self) -> Option<String> {
+        self.source
+            .as_ref()
+            .map(|(origin, from, to)| format!("{origin}@{from}:{to}"))
+    }
+}
+
+fn default_include_in_summary() -> bool {
+    true
+}
+
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<char>>();
+    let mut distances = (0..=b.len()).collect::<Vec<usize>>();
+
+    for (i, a) in a.chars().enumerate() {
+        let mut previous = distances[0];
+        distances[0] = i + 1;
+
+        for (j, b) in b.iter().enumerate() {
+            let current = distances[j + 1];
+            distances[j + 1] = if a == *b {
+                previous
+            } else {
+                1 + previous.min(current).min(distances[j])
+            };
+            previous = current;
+        }
+    }
+
+    distances[b.len()]
+}
+
+fn deserialize_code<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<String>, D::Error> {
+    let code: Option<String> = Option::deserialize(deserializer)?;
+
+    Ok(code.filter(|code| !code.trim().is_empty()))
 }
 
 /// Returns an error `Issue` from anything that derives `std::error::Error`.
 ///
+/// Use `Issue::from_error_with` for a different severity.
+///
 /// Example:
 ///
 ///```rust
@@ -226,11 +979,15 @@ impl Issue {
 /// let issue: Issue = error.into();
 /// assert_eq!(IssueSeverity::Error, issue.severity);
 /// assert_eq!("No such file or directory (os error 2)", issue.message);
+///
+/// let error: std::io::Error = std::fs::read_to_string("nonexistent_file.txt").unwrap_err();
+/// let issue = Issue::from_error_with(error, IssueSeverity::Warning);
+/// assert_eq!(IssueSeverity::Warning, issue.severity);
 /// ```
 #[doc(hidden)]
 impl<E: std::error::Error> From<E> for Issue {
     fn from(error: E) -> Self {
-        Issue::new(IssueSeverity::Error, error.to_string())
+        Issue::from_error_with(error, IssueSeverity::Error)
     }
 }
 
@@ -282,6 +1039,50 @@ impl std::fmt::Display for IssueSeverity {
     }
 }
 
+/// Parse the issue kind from a string, as displayed, ignoring case.
+///
+/// Example:
+///
+/// ```rust
+/// use ara_reporting::error::Error;
+/// use ara_reporting::issue::IssueSeverity;
+///
+/// assert_eq!("warning".parse::<IssueSeverity>().unwrap(), IssueSeverity::Warning);
+/// assert_eq!("Error".parse::<IssueSeverity>().unwrap(), IssueSeverity::Error);
+/// assert_eq!("BUG".parse::<IssueSeverity>().unwrap(), IssueSeverity::Bug);
+///
+/// for severity in [
+///     IssueSeverity::Note,
+///     IssueSeverity::Help,
+///     IssueSeverity::Warning,
+///     IssueSeverity::Error,
+///     IssueSeverity::Bug,
+/// ] {
+///     assert_eq!(severity.to_string().parse::<IssueSeverity>().unwrap(), severity);
+/// }
+///
+/// assert!(matches!(
+///     "fatal".parse::<IssueSeverity>(),
+///     Err(Error::UnknownSeverity { given }) if given == "fatal"
+/// ));
+/// ```
+impl std::str::FromStr for IssueSeverity {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "error" => Ok(IssueSeverity::Error),
+            "warning" => Ok(IssueSeverity::Warning),
+            "help" => Ok(IssueSeverity::Help),
+            "note" => Ok(IssueSeverity::Note),
+            "bug" => Ok(IssueSeverity::Bug),
+            _ => Err(Error::UnknownSeverity {
+                given: s.to_string(),
+            }),
+        }
+    }
+}
+
 /// Display the issue as a string.
 ///
 /// Example:
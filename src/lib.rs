@@ -1,16 +1,44 @@
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::ops::RangeBounds;
+use std::vec::Drain;
+
 use schemars::JsonSchema;
 use serde::Deserialize;
 use serde::Serialize;
 
+use ara_source::SourceMap;
+
+use crate::annotation::AnnotationType;
+use crate::builder::glob_matches;
+use crate::error::Error;
+use crate::error::SpanError;
+use crate::error::SpanFailure;
+use crate::error::SpanKind;
 use crate::issue::Issue;
 use crate::issue::IssueSeverity;
 
 pub mod annotation;
 pub mod builder;
+pub mod checkstyle;
 pub mod error;
+pub mod github;
 pub mod issue;
+pub mod sarif;
+pub mod streaming;
+pub mod styled;
+
+/// The version of the serialized shape of reports, bumped whenever that shape changes.
+///
+/// It is written in the envelope of `OutputFormat::Json`, and in every object of
+/// `OutputFormat::Ndjson`, other formats follow their own external schema.
+pub const REPORT_SCHEMA_VERSION: &str = "1.2";
 
-pub type ReportCollection<'a> = Vec<&'a Report>;
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct ReportCollection<'a> {
+    pub reports: Vec<&'a Report>,
+    pub footer: Option<ReportFooter>,
+}
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
@@ -18,6 +46,12 @@ pub struct ReportFooter {
     pub message: String,
     pub notes: Vec<String>,
     pub summary: bool,
+    #[serde(default)]
+    pub severity: Option<IssueSeverity>,
+    #[serde(default)]
+    pub severity_bar: bool,
+    #[serde(default = "default_show_when_empty")]
+    pub show_when_empty: bool,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
@@ -25,10 +59,342 @@ pub struct ReportFooter {
 pub struct Report {
     pub issues: Vec<Issue>,
     pub footer: Option<ReportFooter>,
+    #[serde(default)]
+    pub default_origin: Option<String>,
+}
+
+/// An entry of the rule catalog, see `Reportable::rule_catalog`.
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct RuleEntry {
+    pub code: String,
+    pub severity: IssueSeverity,
+    pub count: usize,
+    pub example_message: String,
 }
 
+/// Anything that can be turned into a list of reports.
+///
+/// Example:
+///
+/// ```rust
+/// use ara_reporting::Report;
+/// use ara_reporting::ReportCollection;
+/// use ara_reporting::Reportable;
+/// use ara_reporting::issue::Issue;
+/// use ara_reporting::issue::IssueSeverity;
+///
+/// let first_report = Report::new()
+///     .with_issue(Issue::warning("0001", "..."))
+///     .with_issue(Issue::note("0002", "..."));
+///
+/// let second_report = Report::new()
+///     .with_issue(Issue::error("0003", "..."))
+///     .with_issue(Issue::warning("0004", "..."));
+///
+/// let collection: ReportCollection = vec![&first_report, &second_report].into();
+///
+/// assert_eq!(collection.severity(), Some(IssueSeverity::Error));
+/// assert_eq!(collection.issue_count(), 4);
+///
+/// let counts = collection.counts();
+/// assert_eq!(counts.get(&IssueSeverity::Warning), Some(&2));
+/// assert_eq!(counts.get(&IssueSeverity::Error), Some(&1));
+/// assert_eq!(counts.get(&IssueSeverity::Note), Some(&1));
+/// assert_eq!(counts.get(&IssueSeverity::Bug), None);
+///
+/// let empty = ReportCollection::new();
+/// assert_eq!(empty.severity(), None);
+/// assert_eq!(empty.issue_count(), 0);
+/// assert!(empty.counts().is_empty());
+/// ```
 pub trait Reportable {
     fn to_reports(&self) -> Vec<&Report>;
+
+    /// Returns the footer rendered after all reports, summarizing all of them.
+    fn trailing_footer(&self) -> Option<&ReportFooter> {
+        None
+    }
+
+    /// Returns the highest severity of all issues across all reports.
+    fn severity(&self) -> Option<IssueSeverity> {
+        self.to_reports()
+            .iter()
+            .filter_map(|report| report.severity())
+            .max()
+    }
+
+    /// Returns the number of issues across all reports.
+    fn issue_count(&self) -> usize {
+        self.to_reports()
+            .iter()
+            .map(|report| report.issues.len())
+            .sum()
+    }
+
+    /// Returns a catalog of every unique issue code across all reports, sorted by code.
+    ///
+    /// Issues without a code are not part of the catalog, if the same code is reported with
+    /// different severities, the highest severity is used, and the example message is the
+    /// message of the first issue reported with that code.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// use ara_reporting::Report;
+    /// use ara_reporting::Reportable;
+    /// use ara_reporting::RuleEntry;
+    /// use ara_reporting::issue::Issue;
+    /// use ara_reporting::issue::IssueSeverity;
+    ///
+    /// let report = Report::new()
+    ///     .with_issue(Issue::warning("E0002", "unused variable `$a`"))
+    ///     .with_issue(Issue::error("E0001", "undefined variable `$b`"))
+    ///     .with_issue(Issue::error("E0002", "unused variable `$c`"))
+    ///     .with_issue(Issue::from_string("no code"));
+    ///
+    /// assert_eq!(report.rule_catalog(), vec![
+    ///     RuleEntry {
+    ///         code: "E0001".to_string(),
+    ///         severity: IssueSeverity::Error,
+    ///         count: 1,
+    ///         example_message: "undefined variable `$b`".to_string(),
+    ///     },
+    ///     RuleEntry {
+    ///         code: "E0002".to_string(),
+    ///         severity: IssueSeverity::Error,
+    ///         count: 2,
+    ///         example_message: "unused variable `$a`".to_string(),
+    ///     },
+    /// ]);
+    /// ```
+    fn rule_catalog(&self) -> Vec<RuleEntry> {
+        let mut entries: BTreeMap<&str, RuleEntry> = BTreeMap::new();
+        for report in self.to_reports() {
+            for issue in &report.issues {
+                if let Some(code) = &issue.code {
+                    entries
+                        .entry(code)
+                        .and_modify(|entry| {
+                            entry.severity = entry.severity.max(issue.severity);
+                            entry.count += 1;
+                        })
+                        .or_insert_with(|| RuleEntry {
+                            code: code.clone(),
+                            severity: issue.severity,
+                            count: 1,
+                            example_message: issue.message.clone(),
+                        });
+                }
+            }
+        }
+
+        entries.into_values().collect()
+    }
+
+    /// Returns the number of issues per severity across all reports.
+    ///
+    /// Unlike `Reportable::summary_line`, issues excluded from the summary, see
+    /// `Issue::with_include_in_summary`, are counted.
+    fn counts(&self) -> BTreeMap<IssueSeverity, usize> {
+        let mut counts = BTreeMap::new();
+        self.to_reports().iter().for_each(|report| {
+            report.issues.iter().for_each(|issue| {
+                *counts.entry(issue.severity).or_insert(0) += 1;
+            });
+        });
+
+        counts
+    }
+
+    /// Returns the one-line summary of all issues across all reports, as rendered in footers.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// use ara_reporting::Report;
+    /// use ara_reporting::ReportCollection;
+    /// use ara_reporting::issue::Issue;
+    ///
+    /// let first_report = Report::new()
+    ///     .with_issue(Issue::error("E0001", "..."))
+    ///     .with_issue(Issue::warning("W0001", "...").hidden());
+    /// let second_report = Report::new()
+    ///     .with_issue(Issue::error("E0001", "..."))
+    ///     .with_issue(Issue::note("N0001", "..."));
+    ///
+    /// assert_eq!(first_report.summary_line(), "1 warning(s), 1 error(s), 1 hidden");
+    ///
+    /// let collection = ReportCollection::from(vec![&first_report, &second_report]);
+    /// assert_eq!(collection.summary_line(), "1 note(s), 1 warning(s), 2 error(s), 1 hidden");
+    /// ```
+    fn summary_line(&self) -> String {
+        self.to_reports()
+            .iter()
+            .flat_map(|report| report.issues.iter())
+            .collect::<SummaryCounts>()
+            .line(false)
+    }
+
+    /// Returns the process exit code for all issues across all reports, see
+    /// `Reportable::exit_code_with`, with `1` as the failure code.
+    fn exit_code(&self) -> i32 {
+        self.exit_code_with(1)
+    }
+
+    /// Returns the process exit code for all issues across all reports.
+    ///
+    /// The exit code is based on the highest severity of all issues:
+    ///
+    /// - no issues, notes, or help: `0`.
+    /// - warnings, errors, or bugs: the given `code`.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// use ara_reporting::Report;
+    /// use ara_reporting::ReportCollection;
+    /// use ara_reporting::Reportable;
+    /// use ara_reporting::issue::Issue;
+    ///
+    /// let empty = Report::new();
+    /// assert_eq!(empty.exit_code(), 0);
+    ///
+    /// let help = Report::new()
+    ///     .with_issue(Issue::note("N0001", "..."))
+    ///     .with_issue(Issue::help("H0001", "..."));
+    /// assert_eq!(help.exit_code(), 0);
+    ///
+    /// let warnings = Report::new().with_issue(Issue::warning("W0001", "..."));
+    /// assert_eq!(warnings.exit_code(), 1);
+    /// assert_eq!(warnings.exit_code_with(2), 2);
+    ///
+    /// let errors = Report::new()
+    ///     .with_issue(Issue::warning("W0001", "..."))
+    ///     .with_issue(Issue::error("E0001", "..."));
+    /// assert_eq!(errors.exit_code(), 1);
+    ///
+    /// let collection = ReportCollection::from(vec![&empty, &help]);
+    /// assert_eq!(collection.exit_code(), 0);
+    ///
+    /// let collection = ReportCollection::from(vec![&help, &errors]);
+    /// assert_eq!(collection.exit_code_with(101), 101);
+    /// ```
+    fn exit_code_with(&self, code: i32) -> i32 {
+        match self.severity() {
+            Some(severity) if severity >= IssueSeverity::Warning => code,
+            _ => 0,
+        }
+    }
+}
+
+/// The issue counts summarized by footers.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub(crate) struct SummaryCounts {
+    /// The number of issues of each severity, issues excluded from the summary are not
+    /// counted.
+    pub(crate) severities: BTreeMap<IssueSeverity, usize>,
+    /// The number of hidden issues, issues excluded from the summary are not counted.
+    pub(crate) hidden: usize,
+    /// The number of issues, including issues excluded from the summary.
+    pub(crate) total: usize,
+    /// The highest severity of all issues, including issues excluded from the summary.
+    pub(crate) severity: Option<IssueSeverity>,
+    /// The number of visible issues outside of the region filter of the builder, see
+    /// `ReportBuilder::with_region`.
+    pub(crate) outside_region: usize,
+}
+
+impl SummaryCounts {
+    /// Counts the given issue.
+    pub(crate) fn add(&mut self, issue: &Issue) {
+        self.total += 1;
+        self.severity = self.severity.max(Some(issue.severity));
+
+        if issue.include_in_summary {
+            *self.severities.entry(issue.severity).or_insert(0) += 1;
+            if issue.hidden {
+                self.hidden += 1;
+            }
+        }
+    }
+
+    /// Returns the summary line, listing severities in ascending order unless `descending`
+    /// is set.
+    pub(crate) fn line(&self, descending: bool) -> String {
+        let mut entries = self
+            .severities
+            .iter()
+            .collect::<Vec<(&IssueSeverity, &usize)>>();
+        if descending {
+            entries.reverse();
+        }
+
+        let mut summary = entries
+            .iter()
+            .map(|(severity, count)| format!("{count} {severity}(s)"))
+            .collect::<Vec<String>>();
+
+        if self.hidden > 0 {
+            summary.push(format!("{} hidden", self.hidden));
+        }
+
+        summary.join(", ")
+    }
+}
+
+impl<'i> FromIterator<&'i Issue> for SummaryCounts {
+    fn from_iter<I: IntoIterator<Item = &'i Issue>>(issues: I) -> Self {
+        let mut counts = Self::default();
+        for issue in issues {
+            counts.add(issue);
+        }
+
+        counts
+    }
+}
+
+/// Returns whether the range `from..to` contains the given byte.
+pub(crate) fn range_contains((from, to): (usize, usize), byte: usize) -> bool {
+    from <= byte && byte < to
+}
+
+/// Returns whether the ranges `from..to` share at least one byte, an empty range shares
+/// none.
+pub(crate) fn ranges_overlap(
+    (from, to): (usize, usize),
+    (other_from, other_to): (usize, usize),
+) -> bool {
+    from.max(other_from) < to.min(other_to)
+}
+
+/// Returns the content of the given origin in the given source map.
+pub(crate) fn source_content<'a>(source_map: &'a SourceMap, origin: &str) -> Option<&'a str> {
+    source_map
+        .sources
+        .iter()
+        .find(|source| source.name() == origin)
+        .map(|source| source.content.as_str())
+}
+
+/// Returns the 1-based line and column, in characters, of the given byte offset in the
+/// given content.
+pub(crate) fn line_column(content: &str, offset: usize) -> Result<(usize, usize), Error> {
+    let before = content.get(..offset).ok_or(if offset > content.len() {
+        Error::IndexTooLarge {
+            given: offset,
+            max: content.len(),
+        }
+    } else {
+        Error::InvalidCharBoundary { given: offset }
+    })?;
+
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+
+    Ok((
+        before.matches('\n').count() + 1,
+        before[line_start..].chars().count() + 1,
+    ))
 }
 
 /// A report.
@@ -67,9 +433,28 @@ impl Report {
         Self {
             issues: vec![],
             footer: None,
+            default_origin: None,
         }
     }
 
+    /// Create a new report from anything that derives `std::error::Error`, with the given severity.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// use ara_reporting::issue::IssueSeverity;
+    /// use ara_reporting::Report;
+    ///
+    /// let error: std::io::Error = std::fs::read_to_string("nonexistent_file.txt").unwrap_err();
+    /// let report = Report::from_error_with(error, IssueSeverity::Warning);
+    ///
+    /// assert_eq!(report.issues.len(), 1);
+    /// assert_eq!(report.issues[0].severity, IssueSeverity::Warning);
+    /// ```
+    pub fn from_error_with<E: std::error::Error>(error: E, severity: IssueSeverity) -> Self {
+        Self::new().with_issue(Issue::from_error_with(error, severity))
+    }
+
     /// Add an issue to this report.
     #[must_use]
     pub fn with_issue(mut self, issue: Issue) -> Self {
@@ -78,6 +463,32 @@ impl Report {
         self
     }
 
+    /// Add the given issues to this report, in order.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// use ara_reporting::issue::Issue;
+    /// use ara_reporting::Report;
+    ///
+    /// let issues = vec![Issue::error("E0001", "..."), Issue::warning("W0001", "...")];
+    ///
+    /// let report = Report::new()
+    ///     .with_issue(Issue::note("N0001", "..."))
+    ///     .with_issues(issues);
+    ///
+    /// assert_eq!(report.issues.len(), 3);
+    /// assert_eq!(report.issues[0].code, Some("N0001".to_string()));
+    /// assert_eq!(report.issues[1].code, Some("E0001".to_string()));
+    /// assert_eq!(report.issues[2].code, Some("W0001".to_string()));
+    /// ```
+    #[must_use]
+    pub fn with_issues<I: IntoIterator<Item = Issue>>(mut self, issues: I) -> Self {
+        self.issues.extend(issues);
+
+        self
+    }
+
     /// Add a footer to this report.
     #[must_use]
     pub fn with_footer(mut self, footer: ReportFooter) -> Self {
@@ -86,6 +497,99 @@ impl Report {
         self
     }
 
+    /// Add a note to the footer of this report.
+    ///
+    /// If this report has no footer, a footer without a message, showing the summary, is
+    /// added first. A footer without a message is rendered as its notes only.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// use ara_reporting::issue::Issue;
+    /// use ara_reporting::Report;
+    /// use ara_reporting::ReportFooter;
+    /// # use ara_source::SourceMap;
+    /// # use ara_reporting::builder::ColorChoice;
+    /// # use ara_reporting::builder::ReportBuilder;
+    ///
+    /// let report = Report::new()
+    ///     .with_issue(Issue::error("E0001", "..."))
+    ///     .with_footer_note("run with `--verbose` for details");
+    ///
+    /// let footer = report.footer.as_ref().unwrap();
+    /// assert_eq!(footer.message, "");
+    /// assert!(footer.summary);
+    /// assert_eq!(footer.notes, vec!["run with `--verbose` for details".to_string()]);
+    ///
+    /// # let source_map = SourceMap::new(vec![]);
+    /// let output = ReportBuilder::new(&source_map)
+    ///     .with_colors(ColorChoice::Never)
+    ///     .as_string(&report)
+    ///     .unwrap();
+    /// assert!(output.ends_with("\n\n = run with `--verbose` for details\n = summary: 1 error(s)\n\n"));
+    /// assert!(!output.contains("error: \n"));
+    ///
+    /// let report = Report::new()
+    ///     .with_footer(ReportFooter::new("done").with_note("first note"))
+    ///     .with_footer_note("second note");
+    ///
+    /// assert_eq!(report.footer.unwrap().notes, vec!["first note".to_string(), "second note".to_string()]);
+    /// ```
+    #[must_use]
+    pub fn with_footer_note<S: Into<String>>(mut self, note: S) -> Self {
+        self.footer
+            .get_or_insert_with(|| ReportFooter::new(""))
+            .notes
+            .push(note.into());
+
+        self
+    }
+
+    /// Set the default origin of this report.
+    ///
+    /// Annotations without an origin (see `Annotation::here`) are resolved to the
+    /// default origin when rendering.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// use ara_reporting::Report;
+    /// use ara_reporting::annotation::Annotation;
+    /// use ara_reporting::issue::Issue;
+    /// # use ara_source::source::Source;
+    /// # use ara_source::source::SourceKind;
+    /// # use ara_source::SourceMap;
+    /// # use ara_reporting::builder::ReportBuilder;
+    /// # use ara_reporting::builder::ColorChoice;
+    /// # use ara_reporting::error::Error;
+    ///
+    /// let report = Report::new()
+    ///     .with_issue(
+    ///         Issue::error("E0001", "...")
+    ///             .with_annotation(Annotation::here(9, 13).with_message("defined here")),
+    ///     );
+    ///
+    /// # let source = SourceMap::new(vec![
+    /// #     Source::new(SourceKind::Script, "main.ara", "function main(): void {}"),
+    /// # ]);
+    /// let builder = ReportBuilder::new(&source).with_colors(ColorChoice::Never);
+    ///
+    /// assert!(matches!(builder.as_string(&report), Err(Error::MissingOrigin)));
+    ///
+    /// let report = report.with_default_origin("main.ara");
+    /// assert_eq!(report.default_origin, Some("main.ara".to_string()));
+    ///
+    /// let output = builder.as_string(&report).unwrap();
+    /// assert!(output.contains("main.ara:1:10"));
+    /// assert!(output.contains("defined here"));
+    /// ```
+    #[must_use]
+    pub fn with_default_origin<O: Into<String>>(mut self, origin: O) -> Self {
+        self.default_origin = Some(origin.into());
+
+        self
+    }
+
     /// Returns the highest severity of all issues in this report.
     ///
     /// Example:
@@ -120,66 +624,1126 @@ impl Report {
     pub fn severity(&self) -> Option<IssueSeverity> {
         self.issues.iter().map(|issue| issue.severity).max()
     }
-}
 
-impl Default for Report {
-    fn default() -> Self {
-        Self::new()
-    }
-}
+    /// Read the reports of the given JSON envelope, as written by the JSON output format.
+    ///
+    /// Returns `Error::SchemaVersionMismatch` if the envelope was written with a different
+    /// schema version.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// # use ara_source::SourceMap;
+    /// # use ara_reporting::builder::OutputFormat;
+    /// # use ara_reporting::builder::OutputTarget;
+    /// # use ara_reporting::builder::ReportBuilder;
+    /// use ara_reporting::error::Error;
+    /// use ara_reporting::issue::Issue;
+    /// use ara_reporting::Report;
+    /// use termcolor::NoColor;
+    ///
+    /// let report = Report::new().with_issue(Issue::error("E0001", "..."));
+    ///
+    /// # let source = SourceMap::new(vec![]);
+    /// let mut json = NoColor::new(vec![]);
+    /// ReportBuilder::new(&source)
+    ///     .emit_all(&report, &mut [OutputTarget { format: OutputFormat::Json, writer: &mut json }])
+    ///     .unwrap();
+    ///
+    /// let json = String::from_utf8(json.into_inner()).unwrap();
+    /// assert!(json.starts_with(r#"{"reports":"#));
+    /// assert_eq!(Report::from_json(&json).unwrap(), vec![report]);
+    ///
+    /// let json = r#"{ "schema_version": "0.1", "reports": [] }"#;
+    /// assert!(matches!(
+    ///     Report::from_json(json),
+    ///     Err(Error::SchemaVersionMismatch { given, .. }) if given == "0.1"
+    /// ));
+    /// ```
+    pub fn from_json(json: &str) -> Result<Vec<Report>, Error> {
+        let mut envelope =
+            serde_json::from_str::<serde_json::Value>(json).map_err(Error::Serialization)?;
 
-impl std::fmt::Display for Report {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for issue in &self.issues {
-            writeln!(f, "{issue}")?;
+        let given = envelope["schema_version"].as_str().unwrap_or_default();
+        if given != REPORT_SCHEMA_VERSION {
+            return Err(Error::SchemaVersionMismatch {
+                expected: REPORT_SCHEMA_VERSION.to_string(),
+                given: given.to_string(),
+            });
         }
 
-        Ok(())
+        serde_json::from_value(envelope["reports"].take()).map_err(Error::Serialization)
     }
-}
 
-impl From<Issue> for Report {
-    fn from(val: Issue) -> Self {
+    /// Returns a copy of this report, keeping only the issues of exactly the given severity.
+    ///
+    /// The footer and the default origin are kept, and every kept issue is cloned.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// use ara_reporting::issue::Issue;
+    /// use ara_reporting::issue::IssueSeverity;
+    /// use ara_reporting::Report;
+    /// use ara_reporting::ReportFooter;
+    ///
+    /// let report = Report::new()
+    ///     .with_issue(Issue::error("E0001", "..."))
+    ///     .with_issue(Issue::warning("W0001", "..."))
+    ///     .with_issue(Issue::bug("B0001", "..."))
+    ///     .with_issue(Issue::warning("W0002", "..."))
+    ///     .with_footer(ReportFooter::new("done"));
+    ///
+    /// let warnings = report.only_severity(IssueSeverity::Warning);
+    ///
+    /// assert_eq!(warnings.issues.len(), 2);
+    /// assert!(warnings.issues.iter().all(|issue| issue.severity == IssueSeverity::Warning));
+    /// assert_eq!(warnings.footer, report.footer);
+    /// ```
+    pub fn only_severity(&self, severity: IssueSeverity) -> Report {
         Report {
-            issues: vec![val],
-            footer: None,
+            issues: self
+                .issues
+                .iter()
+                .filter(|issue| issue.severity == severity)
+                .cloned()
+                .collect(),
+            footer: self.footer.clone(),
+            default_origin: self.default_origin.clone(),
         }
     }
-}
 
-/// Returns a report from anything that derives `std::error::Error`.
-///
-/// Example:
-///
-///```rust
-/// use ara_reporting::issue::IssueSeverity;
-/// use ara_reporting::Report;
-///
-/// let error: std::io::Error = std::fs::read_to_string("nonexistent_file.txt").unwrap_err();
-/// let report: Report = error.into();
-/// assert_eq!(report.issues.len(), 1);
-///
-/// let issue = report.issues.first().unwrap();
-/// assert_eq!(IssueSeverity::Error, issue.severity);
-/// assert_eq!(issue.message, "No such file or directory (os error 2)");
-/// ```
-#[doc(hidden)]
-impl<E: std::error::Error> From<E> for Report {
-    fn from(error: E) -> Self {
-        Report::new().with_issue(error.into())
+    /// Returns a copy of this report containing only the issues with at least the given severity.
+    ///
+    /// The footer is preserved as-is.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// use ara_reporting::issue::Issue;
+    /// use ara_reporting::issue::IssueSeverity;
+    /// use ara_reporting::Report;
+    /// use ara_reporting::ReportFooter;
+    /// # use ara_source::SourceMap;
+    /// # use ara_reporting::builder::ColorChoice;
+    /// # use ara_reporting::builder::ReportBuilder;
+    ///
+    /// let report = Report::new()
+    ///     .with_issue(Issue::note("N0001", "..."))
+    ///     .with_issue(Issue::warning("W0001", "..."))
+    ///     .with_issue(Issue::error("E0001", "..."))
+    ///     .with_issue(Issue::bug("B0001", "..."))
+    ///     .with_footer(ReportFooter::new("done"));
+    ///
+    /// let fatal = report.filtered_by_severity(IssueSeverity::Error);
+    ///
+    /// assert_eq!(fatal.issues.len(), 2);
+    /// assert_eq!(fatal.footer, report.footer);
+    ///
+    /// # let source_map = SourceMap::new(vec![]);
+    /// let builder = ReportBuilder::new(&source_map).with_colors(ColorChoice::Never);
+    ///
+    /// let output = builder.as_string(&fatal).unwrap();
+    /// assert!(output.contains("summary: 1 error(s), 1 bug(s)"));
+    /// assert!(!output.contains("warning(s)"));
+    ///
+    /// let empty = Report::new()
+    ///     .with_issue(Issue::note("N0001", "..."))
+    ///     .filtered_by_severity(IssueSeverity::Bug);
+    ///
+    /// assert!(empty.issues.is_empty());
+    /// assert!(builder.as_string(&empty).is_ok());
+    /// ```
+    pub fn filtered_by_severity(&self, min: IssueSeverity) -> Report {
+        Report {
+            issues: self
+                .issues
+                .iter()
+                .filter(|issue| issue.severity >= min)
+                .cloned()
+                .collect(),
+            footer: self.footer.clone(),
+            default_origin: self.default_origin.clone(),
+        }
     }
-}
 
-/// A footer for a report.
-///
+    /// Keep only the issues whose source origin matches any of the given globs.
+    ///
+    /// Issues without a source are kept, see `Report::retain_origins_with`.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// use ara_reporting::issue::Issue;
+    /// use ara_reporting::Report;
+    ///
+    /// let mut report = Report::new()
+    ///     .with_issue(Issue::error("E0001", "...").with_source("src/main.ara", 0, 1))
+    ///     .with_issue(Issue::error("E0002", "...").with_source("src/lib/util.ara", 0, 1))
+    ///     .with_issue(Issue::error("E0003", "...").with_source("vendor/foo/lib.ara", 0, 1))
+    ///     .with_issue(Issue::error("E0004", "..."));
+    ///
+    /// report.retain_origins(&["src/**"]);
+    ///
+    /// let codes = report.issues.iter().map(|issue| issue.code.as_deref().unwrap()).collect::<Vec<_>>();
+    /// assert_eq!(codes, vec!["E0001", "E0002", "E0004"]);
+    /// ```
+    pub fn retain_origins(&mut self, globs: &[&str]) {
+        self.retain_origins_with(globs, true);
+    }
+
+    /// Keep only the issues whose source origin matches any of the given globs, and the
+    /// issues without a source if `keep_without_source` is set.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// use ara_reporting::issue::Issue;
+    /// use ara_reporting::Report;
+    ///
+    /// let mut report = Report::new()
+    ///     .with_issue(Issue::error("E0001", "...").with_source("src/main.ara", 0, 1))
+    ///     .with_issue(Issue::error("E0002", "..."));
+    ///
+    /// report.retain_origins_with(&["src/*.ara"], false);
+    ///
+    /// assert_eq!(report.issues.len(), 1);
+    /// assert_eq!(report.issues[0].code, Some("E0001".to_string()));
+    /// ```
+    pub fn retain_origins_with(&mut self, globs: &[&str], keep_without_source: bool) {
+        self.issues.retain(|issue| match &issue.source {
+            Some((origin, _, _)) => globs.iter().any(|glob| glob_matches(glob, origin)),
+            None => keep_without_source,
+        });
+    }
+
+    /// Remove the issues whose source origin matches any of the given globs.
+    ///
+    /// Issues without a source are kept.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// use ara_reporting::issue::Issue;
+    /// use ara_reporting::Report;
+    ///
+    /// let mut report = Report::new()
+    ///     .with_issue(Issue::error("E0001", "...").with_source("src/main.ara", 0, 1))
+    ///     .with_issue(Issue::error("E0002", "...").with_source("vendor/foo/lib.ara", 0, 1))
+    ///     .with_issue(Issue::error("E0003", "...").with_source("target/gen.ara", 0, 1))
+    ///     .with_issue(Issue::error("E0004", "..."));
+    ///
+    /// report.exclude_origins(&["vendor/**", "target/**"]);
+    ///
+    /// let codes = report.issues.iter().map(|issue| issue.code.as_deref().unwrap()).collect::<Vec<_>>();
+    /// assert_eq!(codes, vec!["E0001", "E0004"]);
+    /// ```
+    pub fn exclude_origins(&mut self, globs: &[&str]) {
+        self.issues.retain(|issue| match &issue.source {
+            Some((origin, _, _)) => !globs.iter().any(|glob| glob_matches(glob, origin)),
+            None => true,
+        });
+    }
+
+    /// Remove the issues in the given range from this report, returning them as an iterator.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// use ara_reporting::Report;
+    /// use ara_reporting::issue::Issue;
+    ///
+    /// let mut report = Report::new()
+    ///     .with_issue(Issue::error("E0001", "..."))
+    ///     .with_issue(Issue::warning("W0001", "..."))
+    ///     .with_issue(Issue::error("E0002", "..."));
+    ///
+    /// let warnings = report.drain_issues(1..2).fold(Report::new(), Report::with_issue);
+    /// assert_eq!(warnings.issues.len(), 1);
+    /// assert_eq!(report.issues.len(), 2);
+    ///
+    /// let errors = report.drain_issues(..).fold(Report::new(), Report::with_issue);
+    /// assert_eq!(errors.issues.len(), 2);
+    /// assert!(report.issues.is_empty());
+    /// ```
+    pub fn drain_issues<R: RangeBounds<usize>>(&mut self, range: R) -> Drain<'_, Issue> {
+        self.issues.drain(range)
+    }
+
+    /// Remove all issues from this report, returning them.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// use ara_reporting::Report;
+    /// use ara_reporting::issue::Issue;
+    ///
+    /// let mut report = Report::new()
+    ///     .with_issue(Issue::error("E0001", "..."))
+    ///     .with_issue(Issue::warning("W0001", "..."));
+    ///
+    /// let issues = report.take_issues();
+    /// assert_eq!(issues.len(), 2);
+    /// assert!(report.issues.is_empty());
+    /// ```
+    pub fn take_issues(&mut self) -> Vec<Issue> {
+        std::mem::take(&mut self.issues)
+    }
+
+    /// Returns the one-line summary of the issues of this report, as rendered in footers.
+    pub fn summary_line(&self) -> String {
+        Reportable::summary_line(self)
+    }
+
+    /// Returns the process exit code for the issues of this report, see `Reportable::exit_code`.
+    pub fn exit_code(&self) -> i32 {
+        Reportable::exit_code(self)
+    }
+
+    /// Returns `Ok(value)` if this report has no fatal issues, `Err(self)` otherwise.
+    ///
+    /// An issue is considered fatal if its severity is either `Error` or `Bug`.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// use ara_reporting::Report;
+    /// use ara_reporting::issue::Issue;
+    ///
+    /// let report = Report::new().with_issue(Issue::warning("0001", "..."));
+    /// assert_eq!(report.into_result(42), Ok(42));
+    ///
+    /// let report = Report::new().with_issue(Issue::bug("0001", "..."));
+    /// assert_eq!(report.clone().into_result(42), Err(report));
+    /// ```
+    pub fn into_result<T>(self, value: T) -> Result<T, Report> {
+        if self.severity() >= Some(IssueSeverity::Error) {
+            Err(self)
+        } else {
+            Ok(value)
+        }
+    }
+
+    /// Returns `Ok(self)` if this report has no fatal issues, `Err(self)` otherwise.
+    ///
+    /// An issue is considered fatal if its severity is either `Error` or `Bug`.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// use ara_reporting::Report;
+    /// use ara_reporting::issue::Issue;
+    ///
+    /// fn check(report: Report) -> Result<usize, Report> {
+    ///     let report = report.err_if_fatal()?;
+    ///
+    ///     Ok(report.issues.len())
+    /// }
+    ///
+    /// assert_eq!(check(Report::new().with_issue(Issue::note("0001", "..."))), Ok(1));
+    /// assert!(check(Report::new().with_issue(Issue::error("0001", "..."))).is_err());
+    /// ```
+    pub fn err_if_fatal(self) -> Result<Report, Report> {
+        if self.severity() >= Some(IssueSeverity::Error) {
+            Err(self)
+        } else {
+            Ok(self)
+        }
+    }
+
+    /// Assign an id to each issue of this report, derived from its fingerprint.
+    ///
+    /// Identical issues are assigned the same id, across runs.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// use ara_reporting::Report;
+    /// use ara_reporting::issue::Issue;
+    ///
+    /// let mut report = Report::new()
+    ///     .with_issue(Issue::error("E0417", "...").with_source("main.ara", 10, 14))
+    ///     .with_issue(Issue::warning("W0001", "...").with_source("main.ara", 20, 24));
+    ///
+    /// report.assign_ids();
+    ///
+    /// assert_eq!(report.issues[0].id, Some(report.issues[0].fingerprint()));
+    /// assert_eq!(report.issues[1].id, Some(report.issues[1].fingerprint()));
+    /// assert_ne!(report.issues[0].id, report.issues[1].id);
+    /// ```
+    pub fn assign_ids(&mut self) {
+        for issue in &mut self.issues {
+            issue.id = Some(issue.fingerprint());
+        }
+    }
+
+    /// Rewrite the codes of all issues in this report.
+    ///
+    /// The given function is called for every issue that has a code, returning `Some`
+    /// replaces the code, while returning `None` leaves it unchanged.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// use ara_reporting::Report;
+    /// use ara_reporting::issue::Issue;
+    ///
+    /// let mut report = Report::new()
+    ///     .with_issue(Issue::error("E123", "..."))
+    ///     .with_issue(Issue::warning("W0001", "..."))
+    ///     .with_issue(Issue::from_string("..."));
+    ///
+    /// report.map_codes(|code| {
+    ///     code.strip_prefix('E')
+    ///         .map(|number| format!("ARA-E{:0>4}", number))
+    /// });
+    ///
+    /// assert_eq!(report.issues[0].code, Some("ARA-E0123".to_string()));
+    /// assert_eq!(report.issues[1].code, Some("W0001".to_string()));
+    /// assert_eq!(report.issues[2].code, None);
+    /// ```
+    pub fn map_codes<F: FnMut(&str) -> Option<String>>(&mut self, mut f: F) {
+        for issue in &mut self.issues {
+            if let Some(code) = issue.code.as_deref().and_then(&mut f) {
+                issue.code = Some(code);
+            }
+        }
+    }
+
+    /// Returns every annotation and source span of this report, grouped by origin.
+    ///
+    /// Ranges are sorted, when `merge` is `true`, overlapping and adjacent ranges are merged.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// use ara_reporting::Report;
+    /// use ara_reporting::annotation::Annotation;
+    /// use ara_reporting::issue::Issue;
+    ///
+    /// let report = Report::new()
+    ///     .with_issue(
+    ///         Issue::error("0001", "...")
+    ///             .with_source("main.ara", 10, 14)
+    ///             .with_annotation(Annotation::secondary("main.ara", 2, 5)),
+    ///     )
+    ///     .with_issue(Issue::warning("0002", "...").with_source("main.ara", 12, 20))
+    ///     .with_issue(
+    ///         Issue::note("0003", "...")
+    ///             .with_source("some_file.ara", 5, 7)
+    ///             .with_annotation(Annotation::multi("some_file.ara", vec![(0, 2), (9, 11)])),
+    ///     )
+    ///     .with_issue(Issue::note("0004", "...").with_source("main.ara", 5, 8));
+    ///
+    /// let ranges = report.annotated_ranges(false);
+    /// assert_eq!(ranges["main.ara"], vec![(2, 5), (5, 8), (10, 14), (12, 20)]);
+    /// assert_eq!(ranges["some_file.ara"], vec![(0, 2), (5, 7), (9, 11)]);
+    ///
+    /// let ranges = report.annotated_ranges(true);
+    /// assert_eq!(ranges["main.ara"], vec![(2, 8), (10, 20)]);
+    /// assert_eq!(ranges["some_file.ara"], vec![(0, 2), (5, 7), (9, 11)]);
+    /// ```
+    pub fn annotated_ranges(&self, merge: bool) -> BTreeMap<String, Vec<(usize, usize)>> {
+        let mut ranges: BTreeMap<String, Vec<(usize, usize)>> = BTreeMap::new();
+        for issue in &self.issues {
+            for annotation in &issue.annotations {
+                ranges
+                    .entry(annotation.origin.clone())
+                    .or_default()
+                    .extend(annotation.spans());
+            }
+
+            if let Some((origin, from, to)) = &issue.source {
+                ranges.entry(origin.clone()).or_default().push((*from, *to));
+            }
+        }
+
+        for origin_ranges in ranges.values_mut() {
+            origin_ranges.sort_unstable();
+
+            if merge {
+                let mut merged: Vec<(usize, usize)> = Vec::with_capacity(origin_ranges.len());
+                for (from, to) in origin_ranges.drain(..) {
+                    match merged.last_mut() {
+                        Some((_, last_to)) if from <= *last_to => {
+                            *last_to = (*last_to).max(to);
+                        }
+                        _ => merged.push((from, to)),
+                    }
+                }
+
+                *origin_ranges = merged;
+            }
+        }
+
+        ranges
+    }
+
+    /// Canonicalize this report, so that semantically equal reports compare equal.
+    ///
+    /// Issues are sorted by origin, offset, severity, code, and message, the annotations of
+    /// each issue are sorted by span, and duplicate notes are removed, keeping the first one.
+    ///
+    /// Normalization is lossy: reports where the order of issues, annotations, or notes
+    /// is significant should not be normalized.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// use ara_reporting::Report;
+    /// use ara_reporting::annotation::Annotation;
+    /// use ara_reporting::issue::Issue;
+    ///
+    /// let mut a = Report::new()
+    ///     .with_issue(Issue::warning("W0001", "...").with_source("main.ara", 20, 24))
+    ///     .with_issue(
+    ///         Issue::error("E0001", "...")
+    ///             .with_source("main.ara", 2, 5)
+    ///             .with_annotation(Annotation::secondary("main.ara", 10, 12))
+    ///             .with_annotation(Annotation::secondary("main.ara", 0, 1))
+    ///             .with_note("first")
+    ///             .with_note("second")
+    ///             .with_note("first"),
+    ///     );
+    ///
+    /// let mut b = Report::new()
+    ///     .with_issue(
+    ///         Issue::error("E0001", "...")
+    ///             .with_source("main.ara", 2, 5)
+    ///             .with_annotation(Annotation::secondary("main.ara", 0, 1))
+    ///             .with_annotation(Annotation::secondary("main.ara", 10, 12))
+    ///             .with_note("first")
+    ///             .with_note("second"),
+    ///     )
+    ///     .with_issue(Issue::warning("W0001", "...").with_source("main.ara", 20, 24));
+    ///
+    /// assert_ne!(a, b);
+    ///
+    /// a.normalize();
+    /// b.normalize();
+    ///
+    /// assert_eq!(a, b);
+    /// assert_eq!(a.issues[0].notes, vec!["first".to_string(), "second".to_string()]);
+    /// ```
+    pub fn normalize(&mut self) {
+        for issue in &mut self.issues {
+            issue.annotations.sort_by(|a, b| {
+                (
+                    &a.origin,
+                    a.from,
+                    a.to,
+                    a.r#type != AnnotationType::Primary,
+                    &a.message,
+                )
+                    .cmp(&(
+                        &b.origin,
+                        b.from,
+                        b.to,
+                        b.r#type != AnnotationType::Primary,
+                        &b.message,
+                    ))
+            });
+
+            let mut seen = BTreeSet::new();
+            issue.notes.retain(|note| seen.insert(note.clone()));
+        }
+
+        self.issues.sort_by_cached_key(|issue| {
+            let from = match &issue.source {
+                Some((_, from, _)) => Some(*from),
+                None => issue
+                    .annotations
+                    .iter()
+                    .find(|annotation| annotation.r#type == AnnotationType::Primary)
+                    .map(|annotation| annotation.from),
+            };
+
+            (
+                issue.origin().map(str::to_string),
+                from,
+                issue.severity,
+                issue.code.clone(),
+                issue.message.clone(),
+            )
+        });
+    }
+
+    /// Remove issues equal to an earlier issue of this report, keeping the first occurrence.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// use ara_reporting::Report;
+    /// use ara_reporting::issue::Issue;
+    ///
+    /// let mut report = Report::new()
+    ///     .with_issue(Issue::error("E0001", "...").with_source("main.ara", 10, 12))
+    ///     .with_issue(Issue::warning("W0001", "...").with_source("main.ara", 10, 12))
+    ///     .with_issue(Issue::error("E0001", "...").with_source("main.ara", 10, 12));
+    ///
+    /// report.dedup();
+    ///
+    /// assert_eq!(report.issues, vec![
+    ///     Issue::error("E0001", "...").with_source("main.ara", 10, 12),
+    ///     Issue::warning("W0001", "...").with_source("main.ara", 10, 12),
+    /// ]);
+    /// ```
+    pub fn dedup(&mut self) {
+        let mut unique: Vec<Issue> = Vec::with_capacity(self.issues.len());
+        for issue in self.issues.drain(..) {
+            if !unique.contains(&issue) {
+                unique.push(issue);
+            }
+        }
+
+        self.issues = unique;
+    }
+
+    /// Merge issues sharing the same source span and severity into a single issue.
+    ///
+    /// Issues are merged into the first one, which keeps its code, message and id (taking
+    /// the id of a merged issue if it has none), and takes the highest weight. The messages
+    /// of the other issues, prefixed with their code, are added as notes, followed by their
+    /// own notes, and their annotations are added unless already present. The merged issue
+    /// is hidden only if all merged issues are hidden, and is included in the summary if
+    /// any merged issue is.
+    ///
+    /// Issues without a source, or with different severities, are never merged.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// use ara_reporting::Report;
+    /// use ara_reporting::annotation::Annotation;
+    /// use ara_reporting::issue::Issue;
+    /// use ara_reporting::issue::IssueSeverity;
+    ///
+    /// let mut report = Report::new()
+    ///     .with_issue(
+    ///         Issue::error("E0001", "expected `int`, found `string`")
+    ///             .with_source("main.ara", 10, 12)
+    ///             .with_annotation(Annotation::secondary("main.ara", 0, 4))
+    ///             .with_note("consider casting the value"),
+    ///     )
+    ///     .with_issue(
+    ///         Issue::error("E0005", "variable `$a` is not defined")
+    ///             .with_source("main.ara", 10, 12)
+    ///             .with_annotation(Annotation::secondary("main.ara", 0, 4)),
+    ///     )
+    ///     .with_issue(Issue::warning("W0001", "...").with_source("main.ara", 10, 12))
+    ///     .with_issue(Issue::error("E0002", "...").with_source("main.ara", 20, 24))
+    ///     .with_issue(Issue::error("E0003", "..."))
+    ///     .with_issue(Issue::error("E0004", "..."));
+    ///
+    /// report.merge_adjacent_issues();
+    ///
+    /// assert_eq!(report.issues.len(), 5);
+    /// assert_eq!(report.issues[0].code, Some("E0001".to_string()));
+    /// assert_eq!(report.issues[0].notes, vec![
+    ///     "consider casting the value".to_string(),
+    ///     "E0005: variable `$a` is not defined".to_string(),
+    /// ]);
+    /// assert_eq!(report.issues[0].annotations.len(), 1);
+    /// assert_eq!(report.issues[1].severity, IssueSeverity::Warning);
+    /// ```
+    pub fn merge_adjacent_issues(&mut self) {
+        let mut merged: Vec<Issue> = Vec::with_capacity(self.issues.len());
+        let mut spans: BTreeMap<((String, usize, usize), IssueSeverity), usize> = BTreeMap::new();
+
+        for other in self.issues.drain(..) {
+            let index = match &other.source {
+                Some(source) => match spans.get(&(source.clone(), other.severity)) {
+                    Some(index) => *index,
+                    None => {
+                        spans.insert((source.clone(), other.severity), merged.len());
+                        merged.push(other);
+
+                        continue;
+                    }
+                },
+                None => {
+                    merged.push(other);
+
+                    continue;
+                }
+            };
+
+            let primary = &mut merged[index];
+            primary.notes.push(match &other.code {
+                Some(code) => format!("{}: {}", code, other.message),
+                None => other.message,
+            });
+            primary.notes.extend(other.notes);
+            for annotation in other.annotations {
+                if !primary.annotations.contains(&annotation) {
+                    primary.annotations.push(annotation);
+                }
+            }
+            primary.hidden = primary.hidden && other.hidden;
+            primary.include_in_summary = primary.include_in_summary || other.include_in_summary;
+            primary.id = primary.id.or(other.id);
+            primary.weight = primary.weight.max(other.weight);
+        }
+
+        self.issues = merged;
+    }
+
+    /// Sort the issues of this report by the origin, then the start offset, of their source.
+    ///
+    /// Issues without a source are placed last, and the sort is stable, issues at the same
+    /// position keep their relative order.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// use ara_reporting::Report;
+    /// use ara_reporting::issue::Issue;
+    ///
+    /// let mut report = Report::new()
+    ///     .with_issue(Issue::error("E0001", "..."))
+    ///     .with_issue(Issue::error("E0002", "...").with_source("main.ara", 20, 24))
+    ///     .with_issue(Issue::error("E0003", "...").with_source("lib.ara", 30, 32))
+    ///     .with_issue(Issue::error("E0004", "...").with_source("main.ara", 2, 5))
+    ///     .with_issue(Issue::error("E0005", "..."))
+    ///     .with_issue(Issue::warning("W0001", "...").with_source("main.ara", 2, 3));
+    ///
+    /// report.sort_by_source_position();
+    ///
+    /// let codes = report.issues.iter().map(|issue| issue.code.as_deref().unwrap()).collect::<Vec<_>>();
+    /// assert_eq!(codes, vec!["E0003", "E0004", "W0001", "E0002", "E0001", "E0005"]);
+    /// ```
+    pub fn sort_by_source_position(&mut self) {
+        self.issues.sort_by(|a, b| match (&a.source, &b.source) {
+            (Some((a_origin, a_from, _)), Some((b_origin, b_from, _))) => {
+                (a_origin, a_from).cmp(&(b_origin, b_from))
+            }
+            (Some(_), None) => std::cmp::Ordering::Less,
+            (None, Some(_)) => std::cmp::Ordering::Greater,
+            (None, None) => std::cmp::Ordering::Equal,
+        });
+    }
+
+    /// Returns the source of the first fatal issue (error or bug) in this report.
+    ///
+    /// Returns `None` if there are no fatal issues, or if the first fatal issue has no source.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// use ara_reporting::Report;
+    /// use ara_reporting::issue::Issue;
+    ///
+    /// let report = Report::new()
+    ///     .with_issue(Issue::warning("0001", "...").with_source("main.ara", 1, 2))
+    ///     .with_issue(Issue::error("0002", "...").with_source("main.ara", 10, 11))
+    ///     .with_issue(Issue::bug("0003", "...").with_source("some_file.ara", 9, 10));
+    ///
+    /// assert_eq!(report.first_error_location(), Some(("main.ara".to_string(), 10, 11)));
+    ///
+    /// let report = Report::new()
+    ///     .with_issue(Issue::warning("0001", "...").with_source("main.ara", 1, 2));
+    ///
+    /// assert_eq!(report.first_error_location(), None);
+    ///
+    /// let report = Report::new()
+    ///     .with_issue(Issue::error("0001", "..."))
+    ///     .with_issue(Issue::error("0002", "...").with_source("main.ara", 10, 11));
+    ///
+    /// assert_eq!(report.first_error_location(), None);
+    /// ```
+    pub fn first_error_location(&self) -> Option<(String, usize, usize)> {
+        self.issues
+            .iter()
+            .find(|issue| issue.severity >= IssueSeverity::Error)
+            .and_then(|issue| issue.source.clone())
+    }
+
+    /// Validate the spans of every issue of this report against the given source map.
+    ///
+    /// Returns every invalid span at once, instead of failing at the first one while rendering.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// use ara_source::source::Source;
+    /// use ara_source::source::SourceKind;
+    /// use ara_source::SourceMap;
+    ///
+    /// use ara_reporting::annotation::Annotation;
+    /// use ara_reporting::error::SpanError;
+    /// use ara_reporting::error::SpanFailure;
+    /// use ara_reporting::error::SpanKind;
+    /// use ara_reporting::issue::Issue;
+    /// use ara_reporting::Report;
+    ///
+    /// let source = SourceMap::new(vec![
+    ///     Source::new(SourceKind::Script, "main.ara", "function été(): void {}"),
+    /// ]);
+    ///
+    /// let report = Report::new()
+    ///     .with_issue(Issue::error("E0001", "...").with_source("main.ara", 9, 14))
+    ///     .with_issue(
+    ///         Issue::error("E0002", "...")
+    ///             .with_source("main.ara", 20, 40)
+    ///             .with_annotation(Annotation::secondary("main.ara", 10, 9))
+    ///             .with_annotation(Annotation::secondary("lib.ara", 0, 1)),
+    ///     )
+    ///     .with_issue(Issue::error("E0003", "...").with_annotation(Annotation::primary("main.ara", 0, 10)));
+    ///
+    /// assert_eq!(
+    ///     report.validate_spans(&source),
+    ///     vec![
+    ///         SpanError {
+    ///             issue: 1,
+    ///             kind: SpanKind::Source,
+    ///             origin: "main.ara".to_string(),
+    ///             failure: SpanFailure::OutOfBounds { given: 40, max: 25 },
+    ///         },
+    ///         SpanError {
+    ///             issue: 1,
+    ///             kind: SpanKind::Annotation(0),
+    ///             origin: "main.ara".to_string(),
+    ///             failure: SpanFailure::Inverted { from: 10, to: 9 },
+    ///         },
+    ///         SpanError {
+    ///             issue: 1,
+    ///             kind: SpanKind::Annotation(1),
+    ///             origin: "lib.ara".to_string(),
+    ///             failure: SpanFailure::SourceMissing,
+    ///         },
+    ///         SpanError {
+    ///             issue: 2,
+    ///             kind: SpanKind::Annotation(0),
+    ///             origin: "main.ara".to_string(),
+    ///             failure: SpanFailure::InvalidCharBoundary { given: 10 },
+    ///         },
+    ///     ]
+    /// );
+    /// ```
+    pub fn validate_spans(&self, source_map: &SourceMap) -> Vec<SpanError> {
+        let mut errors = vec![];
+        for (index, issue) in self.issues.iter().enumerate() {
+            let spans = issue
+                .source
+                .iter()
+                .map(|(origin, from, to)| (SpanKind::Source, origin.as_str(), *from, *to))
+                .chain(
+                    issue
+                        .annotations
+                        .iter()
+                        .enumerate()
+                        .flat_map(|(i, annotation)| {
+                            annotation.spans().map(move |(from, to)| {
+                                (
+                                    SpanKind::Annotation(i),
+                                    annotation.origin.as_str(),
+                                    from,
+                                    to,
+                                )
+                            })
+                        }),
+                );
+
+            for (kind, origin, from, to) in spans {
+                let origin = match (origin, self.default_origin.as_deref()) {
+                    ("", Some(default_origin)) => default_origin,
+                    (origin, _) => origin,
+                };
+
+                let mut error = |failure| {
+                    errors.push(SpanError {
+                        issue: index,
+                        kind: kind.clone(),
+                        origin: origin.to_string(),
+                        failure,
+                    });
+                };
+
+                if origin.is_empty() {
+                    error(SpanFailure::MissingOrigin);
+                    continue;
+                }
+
+                let Some(source) = source_map
+                    .sources
+                    .iter()
+                    .find(|source| source.name() == origin)
+                else {
+                    error(SpanFailure::SourceMissing);
+                    continue;
+                };
+
+                if from > to {
+                    error(SpanFailure::Inverted { from, to });
+                    continue;
+                }
+
+                let content = &source.content;
+                if let Some(given) = [from, to].into_iter().find(|given| *given > content.len()) {
+                    error(SpanFailure::OutOfBounds {
+                        given,
+                        max: content.len(),
+                    });
+                } else if let Some(given) = [from, to]
+                    .into_iter()
+                    .find(|given| !content.is_char_boundary(*given))
+                {
+                    error(SpanFailure::InvalidCharBoundary { given });
+                }
+            }
+        }
+
+        errors
+    }
+
+    /// Validate the spans of every issue of this report against the given source map, as
+    /// errors rendering would fail with.
+    ///
+    /// Returns every invalid span at once, see `Report::validate_spans` for the issue and
+    /// origin of each one.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// use ara_source::source::Source;
+    /// use ara_source::source::SourceKind;
+    /// use ara_source::SourceMap;
+    ///
+    /// use ara_reporting::annotation::Annotation;
+    /// use ara_reporting::error::Error;
+    /// use ara_reporting::issue::Issue;
+    /// use ara_reporting::Report;
+    ///
+    /// let source = SourceMap::new(vec![
+    ///     Source::new(SourceKind::Script, "main.ara", "function été(): void {}"),
+    /// ]);
+    ///
+    /// let report = Report::new()
+    ///     .with_issue(Issue::error("E0001", "...").with_source("main.ara", 9, 14));
+    ///
+    /// assert!(report.validate(&source).is_ok());
+    ///
+    /// let report = report
+    ///     .with_issue(Issue::error("E0002", "...").with_source("main.ara", 20, 40))
+    ///     .with_issue(Issue::error("E0003", "...").with_annotation(Annotation::primary("main.ara", 0, 10)))
+    ///     .with_issue(Issue::error("E0004", "...").with_source("main.ara", 14, 9));
+    ///
+    /// let errors = report.validate(&source).unwrap_err();
+    ///
+    /// assert_eq!(errors.len(), 3);
+    /// assert!(matches!(errors[0], Error::IndexTooLarge { given: 40, max: 25 }));
+    /// assert!(matches!(errors[1], Error::InvalidCharBoundary { given: 10 }));
+    /// assert!(matches!(errors[2], Error::InvertedSpan { from: 14, to: 9 }));
+    /// ```
+    pub fn validate(&self, source_map: &SourceMap) -> Result<(), Vec<Error>> {
+        let errors = self
+            .validate_spans(source_map)
+            .into_iter()
+            .map(|error| match error.failure {
+                SpanFailure::MissingOrigin => Error::MissingOrigin,
+                SpanFailure::SourceMissing => Error::FileMissing,
+                SpanFailure::OutOfBounds { given, max } => Error::IndexTooLarge { given, max },
+                SpanFailure::Inverted { from, to } => Error::InvertedSpan { from, to },
+                SpanFailure::InvalidCharBoundary { given } => Error::InvalidCharBoundary { given },
+            })
+            .collect::<Vec<Error>>();
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Returns an indented tree of this report, its issues, their annotations and notes,
+    /// and its footer.
+    ///
+    /// This is not meant for users, but as a readable alternative to `Debug` in test
+    /// failure messages.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// use ara_reporting::annotation::Annotation;
+    /// use ara_reporting::issue::Issue;
+    /// use ara_reporting::Report;
+    /// use ara_reporting::ReportFooter;
+    ///
+    /// let report = Report::new()
+    ///     .with_issue(
+    ///         Issue::error("E0001", "standalone type `void` cannot be part of a union")
+    ///             .with_source("main.ara", 10, 14)
+    ///             .with_annotation(Annotation::secondary("main.ara", 9, 10).with_message("union starts here"))
+    ///             .with_annotation(Annotation::multi("main.ara", vec![(0, 3), (18, 22)]))
+    ///             .with_note("consider using `null` instead"),
+    ///     )
+    ///     .with_issue(Issue::warning("W0001", "unused variable").hidden())
+    ///     .with_footer(ReportFooter::new("done").with_note("1 file analyzed"));
+    ///
+    /// assert_eq!(report.debug_tree(), [
+    ///     "report",
+    ///     "  error[E0001] \"standalone type `void` cannot be part of a union\" at main.ara@10:14",
+    ///     "    secondary main.ara@9:10 \"union starts here\"",
+    ///     "    secondary main.ara@0:3,18:22",
+    ///     "    note \"consider using `null` instead\"",
+    ///     "  warning[W0001] \"unused variable\" (hidden)",
+    ///     "  footer \"done\"",
+    ///     "    note \"1 file analyzed\"",
+    ///     "",
+    /// ].join("\n"));
+    /// ```
+    pub fn debug_tree(&self) -> String {
+        let mut tree = String::from("report");
+        if let Some(origin) = &self.default_origin {
+            tree.push_str(&format!(" (default origin {origin:?})"));
+        }
+        tree.push('\n');
+
+        for issue in &self.issues {
+            tree.push_str(&format!("  {}", issue.severity));
+            if let Some(code) = &issue.code {
+                tree.push_str(&format!("[{code}]"));
+            }
+            tree.push_str(&format!(" {:?}", issue.message));
+            if let Some(location) = issue.byte_location_string() {
+                tree.push_str(&format!(" at {location}"));
+            }
+            if issue.hidden {
+                tree.push_str(" (hidden)");
+            }
+            tree.push('\n');
+
+            for annotation in &issue.annotations {
+                let r#type = match annotation.r#type {
+                    AnnotationType::Primary => "primary",
+                    AnnotationType::Secondary => "secondary",
+                };
+
+                let spans = annotation
+                    .spans()
+                    .map(|(from, to)| format!("{from}:{to}"))
+                    .collect::<Vec<String>>()
+                    .join(",");
+
+                tree.push_str(&format!("    {type} {}@{spans}", annotation.origin));
+                if let Some(message) = &annotation.message {
+                    tree.push_str(&format!(" {message:?}"));
+                }
+                tree.push('\n');
+                if let Some(note) = &annotation.note {
+                    tree.push_str(&format!("      note {note:?}\n"));
+                }
+            }
+
+            for note in &issue.notes {
+                tree.push_str(&format!("    note {note:?}\n"));
+            }
+        }
+
+        if let Some(footer) = &self.footer {
+            tree.push_str(&format!("  footer {:?}\n", footer.message));
+            for note in &footer.notes {
+                tree.push_str(&format!("    note {note:?}\n"));
+            }
+        }
+
+        tree
+    }
+}
+
+impl Default for Report {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Display for Report {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for issue in &self.issues {
+            writeln!(f, "{issue}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl From<Issue> for Report {
+    fn from(val: Issue) -> Self {
+        Report {
+            issues: vec![val],
+            footer: None,
+            default_origin: None,
+        }
+    }
+}
+
+/// Returns a report from anything that derives `std::error::Error`.
+///
+/// The issue of the report is an error, use `Report::from_error_with` for a different severity.
+///
+/// Example:
+///
+///```rust
+/// use ara_reporting::issue::IssueSeverity;
+/// use ara_reporting::Report;
+///
+/// let error: std::io::Error = std::fs::read_to_string("nonexistent_file.txt").unwrap_err();
+/// let report: Report = error.into();
+/// assert_eq!(report.issues.len(), 1);
+///
+/// let issue = report.issues.first().unwrap();
+/// assert_eq!(IssueSeverity::Error, issue.severity);
+/// assert_eq!(issue.message, "No such file or directory (os error 2)");
+///
+/// let error: std::io::Error = std::fs::read_to_string("nonexistent_file.txt").unwrap_err();
+/// let report = Report::from_error_with(error, IssueSeverity::Warning);
+/// assert_eq!(IssueSeverity::Warning, report.issues[0].severity);
+/// ```
+#[doc(hidden)]
+impl<E: std::error::Error> From<E> for Report {
+    fn from(error: E) -> Self {
+        Report::from_error_with(error, IssueSeverity::Error)
+    }
+}
+
+/// A footer for a report.
+///
 /// A footer is a message that is displayed at the end of a report.
 impl ReportFooter {
     /// Create a new footer.
+    ///
+    /// The message may span multiple lines, continuation lines are aligned under the first one.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// use ara_reporting::Report;
+    /// use ara_reporting::ReportFooter;
+    /// use ara_reporting::issue::Issue;
+    /// # use ara_source::SourceMap;
+    /// # use ara_reporting::builder::ReportBuilder;
+    /// # use ara_reporting::builder::ColorChoice;
+    ///
+    /// let report = Report::new()
+    ///     .with_issue(Issue::warning("W0001", "..."))
+    ///     .with_footer(ReportFooter::new("analysis finished\nsome files were skipped").with_summary(false));
+    ///
+    /// # let source_map = SourceMap::new(vec![]);
+    /// let output = ReportBuilder::new(&source_map)
+    ///     .with_colors(ColorChoice::Never)
+    ///     .as_string(&report)
+    ///     .unwrap();
+    ///
+    /// assert!(output.ends_with("warning: analysis finished\n         some files were skipped\n\n"));
+    /// ```
     pub fn new<M: Into<String>>(message: M) -> Self {
         Self {
             message: message.into(),
             notes: vec![],
             summary: true,
+            severity: None,
+            severity_bar: false,
+            show_when_empty: true,
         }
     }
 
@@ -191,6 +1755,41 @@ impl ReportFooter {
         self
     }
 
+    /// Set the severity of this footer.
+    ///
+    /// By default, the footer uses the highest severity of the report issues.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// use ara_reporting::Report;
+    /// use ara_reporting::ReportFooter;
+    /// use ara_reporting::issue::Issue;
+    /// use ara_reporting::issue::IssueSeverity;
+    /// # use ara_source::SourceMap;
+    /// # use ara_reporting::builder::ReportBuilder;
+    /// # use ara_reporting::builder::ColorChoice;
+    ///
+    /// let report = Report::new()
+    ///     .with_issue(Issue::error("E0001", "..."))
+    ///     .with_footer(ReportFooter::new("analysis finished").with_severity(IssueSeverity::Note));
+    ///
+    /// # let source_map = SourceMap::new(vec![]);
+    /// let output = ReportBuilder::new(&source_map)
+    ///     .with_colors(ColorChoice::Never)
+    ///     .as_string(&report)
+    ///     .unwrap();
+    ///
+    /// assert!(output.contains("note: analysis finished"));
+    /// assert!(!output.contains("error: analysis finished"));
+    /// ```
+    #[must_use]
+    pub fn with_severity(mut self, severity: IssueSeverity) -> Self {
+        self.severity = Some(severity);
+
+        self
+    }
+
     /// Defines if either the summary should be enabled or disabled
     #[must_use]
     pub fn with_summary(mut self, enabled: bool) -> Self {
@@ -198,6 +1797,85 @@ impl ReportFooter {
 
         self
     }
+
+    /// Defines if a bar showing the proportion of issues of each severity should be rendered
+    /// above the summary.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// # use ara_source::SourceMap;
+    /// # use ara_reporting::builder::CharSet;
+    /// # use ara_reporting::builder::ColorChoice;
+    /// # use ara_reporting::builder::ReportBuilder;
+    /// # use ara_reporting::issue::Issue;
+    /// # use ara_reporting::Report;
+    /// # use ara_reporting::ReportFooter;
+    /// # use ara_reporting::styled::Role;
+    /// let mut report = Report::new().with_footer(ReportFooter::new("done").with_severity_bar(true));
+    /// for _ in 0..6 {
+    ///     report = report.with_issue(Issue::error("E0001", "..."));
+    /// }
+    /// for _ in 0..4 {
+    ///     report = report.with_issue(Issue::warning("W0001", "..."));
+    /// }
+    ///
+    /// # let source = SourceMap::new(vec![]);
+    /// let builder = ReportBuilder::new(&source).with_colors(ColorChoice::Never);
+    ///
+    /// let output = builder.as_string(&report).unwrap();
+    /// assert!(output.contains(
+    ///     " = [############========] 6 error(s) / 4 warning(s)\n = summary: 4 warning(s), 6 error(s)\n"
+    /// ));
+    ///
+    /// let output = builder.with_charset(CharSet::Unicode).as_string(&report).unwrap();
+    /// assert!(output.contains(" = [████████████▓▓▓▓▓▓▓▓] 6 error(s) / 4 warning(s)\n"));
+    ///
+    /// let lines = ReportBuilder::new(&source).to_render_lines(&report).unwrap();
+    /// let (_, style) = lines.iter().flatten().find(|(text, _)| text == "############").unwrap();
+    /// assert_eq!(style.role, Some(Role::HeaderError));
+    /// ```
+    #[must_use]
+    pub fn with_severity_bar(mut self, enabled: bool) -> Self {
+        self.severity_bar = enabled;
+
+        self
+    }
+
+    /// Defines if the footer should be rendered for a report without issues.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// # use ara_source::SourceMap;
+    /// # use ara_reporting::builder::ColorChoice;
+    /// # use ara_reporting::builder::ReportBuilder;
+    /// # use ara_reporting::issue::Issue;
+    /// # use ara_reporting::Report;
+    /// # use ara_reporting::ReportFooter;
+    /// # let source = SourceMap::new(vec![]);
+    /// let builder = ReportBuilder::new(&source).with_colors(ColorChoice::Never);
+    /// let footer = ReportFooter::new("analysis finished").with_show_when_empty(false);
+    ///
+    /// let report = Report::new().with_footer(footer.clone());
+    /// assert_eq!(builder.as_string(&report).unwrap(), "");
+    ///
+    /// let report = Report::new()
+    ///     .with_issue(Issue::warning("W0001", "..."))
+    ///     .with_footer(footer);
+    /// let output = builder.as_string(&report).unwrap();
+    /// assert!(output.contains("warning: analysis finished"));
+    /// ```
+    #[must_use]
+    pub fn with_show_when_empty(mut self, enabled: bool) -> Self {
+        self.show_when_empty = enabled;
+
+        self
+    }
+}
+
+fn default_show_when_empty() -> bool {
+    true
 }
 
 impl Reportable for Report {
@@ -206,8 +1884,128 @@ impl Reportable for Report {
     }
 }
 
-impl Reportable for ReportCollection<'_> {
+impl Reportable for Vec<&Report> {
     fn to_reports(&self) -> Vec<&Report> {
         self.to_vec()
     }
 }
+
+/// A collection of reports.
+///
+/// Example:
+///
+/// ```rust
+/// use ara_reporting::Report;
+/// use ara_reporting::ReportCollection;
+/// use ara_reporting::ReportFooter;
+/// use ara_reporting::issue::Issue;
+/// use ara_reporting::issue::IssueSeverity;
+///
+/// let first_report = Report::new().with_issue(Issue::warning("0001", "..."));
+/// let second_report = Report::new().with_issue(Issue::error("0002", "..."));
+///
+/// let mut collection = ReportCollection::new().with_footer(ReportFooter::new("done"));
+/// collection.push(&first_report);
+/// collection.push(&second_report);
+///
+/// assert_eq!(collection.iter().count(), 2);
+/// assert_eq!(collection.severity(), Some(IssueSeverity::Error));
+/// assert_eq!(collection.counts().get(&IssueSeverity::Warning), Some(&1));
+/// assert_eq!(collection.footer.as_ref().unwrap().message, "done");
+/// ```
+impl<'a> ReportCollection<'a> {
+    /// Create a new empty collection.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a report to this collection.
+    pub fn push(&mut self, report: &'a Report) {
+        self.reports.push(report);
+    }
+
+    /// Returns an iterator over the reports of this collection.
+    pub fn iter(&self) -> impl Iterator<Item = &&'a Report> {
+        self.reports.iter()
+    }
+
+    /// Returns the highest severity of all issues across all reports.
+    pub fn severity(&self) -> Option<IssueSeverity> {
+        Reportable::severity(self)
+    }
+
+    /// Returns the number of issues per severity across all reports, see
+    /// `Reportable::counts`.
+    pub fn counts(&self) -> BTreeMap<IssueSeverity, usize> {
+        Reportable::counts(self)
+    }
+
+    /// Returns the one-line summary of all issues across all reports.
+    pub fn summary_line(&self) -> String {
+        Reportable::summary_line(self)
+    }
+
+    /// Returns the process exit code for all issues across all reports, see
+    /// `Reportable::exit_code`.
+    pub fn exit_code(&self) -> i32 {
+        Reportable::exit_code(self)
+    }
+
+    /// Set the footer rendered after all reports of this collection.
+    ///
+    /// Unlike the footers of each report, the summary of this footer counts the issues
+    /// of all reports.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// # use ara_source::SourceMap;
+    /// # use ara_reporting::builder::ColorChoice;
+    /// # use ara_reporting::builder::ReportBuilder;
+    /// # use ara_reporting::issue::Issue;
+    /// # use ara_reporting::Report;
+    /// # use ara_reporting::ReportCollection;
+    /// # use ara_reporting::ReportFooter;
+    /// let first_report = Report::new().with_issue(Issue::warning("0001", "..."));
+    /// let second_report = Report::new()
+    ///     .with_issue(Issue::error("0002", "..."))
+    ///     .with_footer(ReportFooter::new("second report"));
+    ///
+    /// let collection = ReportCollection::from(vec![&first_report, &second_report])
+    ///     .with_footer(ReportFooter::new("all reports"));
+    ///
+    /// # let source = SourceMap::new(vec![]);
+    /// let output = ReportBuilder::new(&source)
+    ///     .with_colors(ColorChoice::Never)
+    ///     .as_string(&collection)
+    ///     .unwrap();
+    ///
+    /// assert!(output.contains("error: second report\n = summary: 1 error(s)\n"));
+    /// assert!(output.ends_with("error: all reports\n = summary: 1 warning(s), 1 error(s)\n\n"));
+    /// ```
+    #[must_use]
+    pub fn with_footer(mut self, footer: ReportFooter) -> Self {
+        self.footer = Some(footer);
+
+        self
+    }
+}
+
+impl Reportable for ReportCollection<'_> {
+    fn to_reports(&self) -> Vec<&Report> {
+        self.reports.to_vec()
+    }
+
+    fn trailing_footer(&self) -> Option<&ReportFooter> {
+        self.footer.as_ref()
+    }
+}
+
+impl<'a> From<Vec<&'a Report>> for ReportCollection<'a> {
+    fn from(reports: Vec<&'a Report>) -> Self {
+        Self {
+            reports,
+            footer: None,
+        }
+    }
+}
@@ -0,0 +1,195 @@
+use ara_source::SourceMap;
+use serde_json::json;
+use serde_json::Value;
+
+use crate::annotation::AnnotationType;
+use crate::builder::resolve_origins;
+use crate::builder::ReportBuilder;
+use crate::error::Error;
+use crate::issue::Issue;
+use crate::issue::IssueSeverity;
+use crate::Reportable;
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const SARIF_VERSION: &str = "2.1.0";
+
+/// Get the report as a SARIF 2.1.0 log, rendered with a default builder.
+///
+/// This is a shorthand for `ReportBuilder::new(source_map).as_sarif(reportable)`, see
+/// `ReportBuilder::as_sarif`.
+///
+/// Example:
+///
+/// ```rust
+/// use ara_source::source::Source;
+/// use ara_source::source::SourceKind;
+/// use ara_source::SourceMap;
+///
+/// use ara_reporting::annotation::Annotation;
+/// use ara_reporting::issue::Issue;
+/// use ara_reporting::sarif::to_sarif;
+/// use ara_reporting::Report;
+///
+/// let source = SourceMap::new(vec![
+///     Source::new(SourceKind::Script, "main.ara", "function main(): void {\n  $a = 1;\n}"),
+/// ]);
+///
+/// let report = Report::new().with_issue(
+///     Issue::warning("W0001", "unused variable `$a`")
+///         .with_source("main.ara", 26, 28)
+///         .with_annotation(Annotation::secondary("main.ara", 9, 13).with_message("in this function")),
+/// );
+///
+/// let sarif = to_sarif(&report, &source).unwrap();
+/// let sarif: serde_json::Value = serde_json::from_str(&sarif).unwrap();
+///
+/// assert_eq!(sarif["version"], "2.1.0");
+///
+/// let result = &sarif["runs"][0]["results"][0];
+/// assert_eq!(result["ruleId"], "W0001");
+/// assert_eq!(result["level"], "warning");
+/// assert_eq!(result["message"]["text"], "unused variable `$a`");
+///
+/// let location = &result["locations"][0]["physicalLocation"];
+/// assert_eq!(location["artifactLocation"]["uri"], "main.ara");
+/// assert_eq!(location["region"], serde_json::json!({
+///     "startLine": 2,
+///     "startColumn": 3,
+///     "endLine": 2,
+///     "endColumn": 5,
+///     "byteOffset": 26,
+///     "byteLength": 2,
+/// }));
+///
+/// let related = &result["relatedLocations"][0];
+/// assert_eq!(related["message"]["text"], "in this function");
+/// assert_eq!(related["physicalLocation"]["region"]["startColumn"], 10);
+/// ```
+pub fn to_sarif(reportable: &dyn Reportable, source_map: &SourceMap) -> Result<String, Error> {
+    ReportBuilder::new(source_map).as_sarif(reportable)
+}
+
+/// Returns the SARIF log of the issues the given builder would render.
+pub(crate) fn sarif(builder: &ReportBuilder, reportable: &dyn Reportable) -> Result<String, Error> {
+    let mut results = vec![];
+    let mut rules: Vec<&str> = vec![];
+
+    for report in reportable.to_reports() {
+        let (issues, _) = builder.visible_issues(report);
+        for issue in issues {
+            if let Some(code) = &issue.code {
+                if !rules.contains(&code.as_str()) {
+                    rules.push(code);
+                }
+            }
+
+            let issue = resolve_origins(issue, report.default_origin.as_deref())?;
+
+            results.push(result(builder, &issue));
+        }
+    }
+
+    let log = json!({
+        "$schema": SARIF_SCHEMA,
+        "version": SARIF_VERSION,
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": env!("CARGO_PKG_NAME"),
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "rules": rules.iter().map(|id| json!({ "id": id })).collect::<Vec<Value>>(),
+                },
+            },
+            "columnKind": "unicodeCodePoints",
+            "results": results,
+        }],
+    });
+
+    serde_json::to_string(&log).map_err(Error::Serialization)
+}
+
+/// Returns the SARIF `result` of the given issue.
+fn result(builder: &ReportBuilder, issue: &Issue) -> Value {
+    let mut locations = vec![];
+    let mut related = vec![];
+
+    if let Some((origin, from, to)) = &issue.source {
+        locations.push(location(builder, origin, *from, *to, None));
+    }
+
+    for annotation in &issue.annotations {
+        for (from, to) in annotation.spans() {
+            let location = location(
+                builder,
+                &annotation.origin,
+                from,
+                to,
+                annotation.message.as_deref(),
+            );
+
+            match annotation.r#type {
+                AnnotationType::Primary => locations.push(location),
+                AnnotationType::Secondary => related.push(location),
+            }
+        }
+    }
+
+    let mut result = json!({
+        "level": match issue.severity {
+            IssueSeverity::Bug | IssueSeverity::Error => "error",
+            IssueSeverity::Warning => "warning",
+            IssueSeverity::Note | IssueSeverity::Help => "note",
+        },
+        "message": {
+            "text": issue.message,
+        },
+        "locations": locations,
+    });
+
+    if let Some(code) = &issue.code {
+        result["ruleId"] = json!(code);
+    }
+
+    if !related.is_empty() {
+        result["relatedLocations"] = json!(related);
+    }
+
+    result
+}
+
+/// Returns the SARIF `location` of the given span.
+fn location(
+    builder: &ReportBuilder,
+    origin: &str,
+    from: usize,
+    to: usize,
+    message: Option<&str>,
+) -> Value {
+    let mut physical = json!({
+        "artifactLocation": {
+            "uri": origin,
+        },
+    });
+
+    if let (Some((start_line, start_column)), Some((end_line, end_column))) =
+        (builder.locate(origin, from), builder.locate(origin, to))
+    {
+        let (_, byte_offset) = builder.source_offset(origin);
+
+        physical["region"] = json!({
+            "startLine": start_line,
+            "startColumn": start_column,
+            "endLine": end_line,
+            "endColumn": end_column,
+            "byteOffset": from + byte_offset,
+            "byteLength": to.saturating_sub(from),
+        });
+    }
+
+    let mut location = json!({ "physicalLocation": physical });
+    if let Some(message) = message {
+        location["message"] = json!({ "text": message });
+    }
+
+    location
+}
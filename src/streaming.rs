@@ -0,0 +1,129 @@
+use codespan_reporting::files::SimpleFiles;
+use codespan_reporting::term::Config;
+use rustc_hash::FxHashMap;
+use termcolor::WriteColor;
+
+use crate::builder::resolve_origins;
+use crate::builder::EncodingPolicy;
+use crate::builder::ReportBuilder;
+use crate::error::Error;
+use crate::issue::Issue;
+use crate::ReportFooter;
+use crate::SummaryCounts;
+
+pub struct StreamingReporter<'a, T: WriteColor> {
+    pub builder: ReportBuilder<'a>,
+    writer: T,
+    config: Config,
+    files: SimpleFiles<&'a str, &'a String>,
+    files_ids: FxHashMap<String, usize>,
+    default_origin: Option<String>,
+    counts: SummaryCounts,
+    emitted: usize,
+}
+
+/// A streaming reporter.
+///
+/// A streaming reporter renders issues as soon as they are emitted, instead of
+/// rendering a whole report at once. Emitted issues are not kept, only their counts are,
+/// for the footer to summarize.
+///
+/// Example:
+///
+/// ```rust
+/// use ara_source::source::Source;
+/// use ara_source::source::SourceKind;
+/// use ara_source::SourceMap;
+/// use termcolor::NoColor;
+///
+/// use ara_reporting::builder::ReportBuilder;
+/// use ara_reporting::issue::Issue;
+/// use ara_reporting::streaming::StreamingReporter;
+/// use ara_reporting::ReportFooter;
+///
+/// let source = SourceMap::new(vec![
+///     Source::new(SourceKind::Script, "main.ara", "function main(): void {}"),
+/// ]);
+///
+/// let mut reporter = StreamingReporter::new(ReportBuilder::new(&source), NoColor::new(vec![]));
+///
+/// reporter.emit_issue(&Issue::error("E0001", "first").with_source("main.ara", 9, 13)).unwrap();
+/// reporter.emit_issue(&Issue::warning("W0001", "second").with_source("main.ara", 0, 8)).unwrap();
+///
+/// let writer = reporter.finish(ReportFooter::new("done")).unwrap();
+/// let output = String::from_utf8(writer.into_inner()).unwrap();
+///
+/// assert!(output.starts_with("error[E0001]: first"));
+/// assert!(output.contains("warning[W0001]: second"));
+/// assert!(output.contains("summary: 1 warning(s), 1 error(s)"));
+/// ```
+impl<'a, T: WriteColor> StreamingReporter<'a, T> {
+    /// Create a new streaming reporter, rendering into the given writer.
+    pub fn new(builder: ReportBuilder<'a>, writer: T) -> Self {
+        let config = builder.config();
+        let (files, files_ids) = builder.files();
+
+        Self {
+            builder,
+            writer,
+            config,
+            files,
+            files_ids,
+            default_origin: None,
+            counts: SummaryCounts::default(),
+            emitted: 0,
+        }
+    }
+
+    /// Set the default origin used to resolve annotations without an origin.
+    #[must_use]
+    pub fn with_default_origin<O: Into<String>>(mut self, origin: O) -> Self {
+        self.default_origin = Some(origin.into());
+
+        self
+    }
+
+    /// Render the given issue immediately.
+    ///
+    /// Hidden issues, issues below the minimum severity of their origin, and issues outside
+    /// of the region filter are not rendered, but are still counted in the footer summary.
+    pub fn emit_issue(&mut self, issue: &Issue) -> Result<(), Error> {
+        if !issue.hidden && !self.builder.is_suppressed(issue) && self.builder.is_in_region(issue) {
+            let issue = resolve_origins(issue, self.default_origin.as_deref())?;
+
+            if self.builder.encoding_policy == EncodingPolicy::Strict {
+                self.builder.check_encoding(&issue)?;
+            }
+
+            if self.emitted > 0 {
+                self.builder.write_separator(&mut self.writer)?;
+            }
+
+            self.builder.write_issue(
+                &mut self.writer,
+                &self.config,
+                &self.files,
+                &self.files_ids,
+                &issue,
+            )?;
+            self.emitted += 1;
+        }
+
+        self.builder.count(&mut self.counts, issue);
+
+        Ok(())
+    }
+
+    /// Render the given footer, summarizing all emitted issues, and return the writer.
+    pub fn finish(mut self, footer: ReportFooter) -> Result<T, Error> {
+        self.builder.write_footer(
+            &mut self.writer,
+            &self.config,
+            &self.files,
+            &self.counts,
+            &footer,
+        )?;
+
+        Ok(self.writer)
+    }
+}
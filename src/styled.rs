@@ -0,0 +1,373 @@
+use std::sync::OnceLock;
+
+use codespan_reporting::term::Styles;
+use termcolor::Color;
+use termcolor::ColorSpec;
+use termcolor::WriteColor;
+
+pub type StyledLine = Vec<(String, Style)>;
+
+pub type HtmlLine = Vec<HtmlSegment>;
+
+/// A segment of rendered text, along with the CSS class to style it with.
+///
+/// The text is not escaped.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct HtmlSegment {
+    pub text: String,
+    pub class: Option<&'static str>,
+}
+
+/// The element of the report a segment renders.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Role {
+    HeaderBug,
+    HeaderError,
+    HeaderWarning,
+    HeaderNote,
+    HeaderHelp,
+    HeaderMessage,
+    PrimaryLabelBug,
+    PrimaryLabelError,
+    PrimaryLabelWarning,
+    PrimaryLabelNote,
+    PrimaryLabelHelp,
+    SecondaryLabel,
+    LineNumber,
+    SourceBorder,
+    NoteBullet,
+    FileBanner,
+    NoteCode,
+}
+
+impl Role {
+    const ALL: [Role; 17] = [
+        Role::HeaderBug,
+        Role::HeaderError,
+        Role::HeaderWarning,
+        Role::HeaderNote,
+        Role::HeaderHelp,
+        Role::HeaderMessage,
+        Role::PrimaryLabelBug,
+        Role::PrimaryLabelError,
+        Role::PrimaryLabelWarning,
+        Role::PrimaryLabelNote,
+        Role::PrimaryLabelHelp,
+        Role::SecondaryLabel,
+        Role::LineNumber,
+        Role::SourceBorder,
+        Role::NoteBullet,
+        Role::FileBanner,
+        Role::NoteCode,
+    ];
+
+    /// Returns the CSS class of the role.
+    #[must_use]
+    pub fn class(self) -> &'static str {
+        match self {
+            Role::HeaderBug => "severity-bug",
+            Role::HeaderError => "severity-error",
+            Role::HeaderWarning => "severity-warning",
+            Role::HeaderNote => "severity-note",
+            Role::HeaderHelp => "severity-help",
+            Role::HeaderMessage => "header-message",
+            Role::PrimaryLabelBug
+            | Role::PrimaryLabelError
+            | Role::PrimaryLabelWarning
+            | Role::PrimaryLabelNote
+            | Role::PrimaryLabelHelp => "annotation-primary",
+            Role::SecondaryLabel => "annotation-secondary",
+            Role::LineNumber => "line-number",
+            Role::SourceBorder => "source-border",
+            Role::NoteBullet => "note-bullet",
+            Role::FileBanner => "file-banner",
+            Role::NoteCode => "note-code",
+        }
+    }
+
+    /// Returns the spec the role is rendered with, elements that are not part of the
+    /// codespan styles share a single spec.
+    pub(crate) fn spec(self, styles: &Styles) -> &ColorSpec {
+        match self {
+            Role::HeaderBug => &styles.header_bug,
+            Role::HeaderError => &styles.header_error,
+            Role::HeaderWarning => &styles.header_warning,
+            Role::HeaderNote => &styles.header_note,
+            Role::HeaderHelp => &styles.header_help,
+            Role::HeaderMessage => &styles.header_message,
+            Role::PrimaryLabelBug => &styles.primary_label_bug,
+            Role::PrimaryLabelError => &styles.primary_label_error,
+            Role::PrimaryLabelWarning => &styles.primary_label_warning,
+            Role::PrimaryLabelNote => &styles.primary_label_note,
+            Role::PrimaryLabelHelp => &styles.primary_label_help,
+            Role::SecondaryLabel => &styles.secondary_label,
+            Role::LineNumber => &styles.line_number,
+            Role::SourceBorder => &styles.source_border,
+            Role::NoteBullet => &styles.note_bullet,
+            Role::FileBanner => {
+                static SPEC: OnceLock<ColorSpec> = OnceLock::new();
+
+                SPEC.get_or_init(|| {
+                    let mut spec = ColorSpec::new();
+                    spec.set_bold(true);
+
+                    spec
+                })
+            }
+            Role::NoteCode => {
+                static SPEC: OnceLock<ColorSpec> = OnceLock::new();
+
+                SPEC.get_or_init(|| {
+                    let mut spec = ColorSpec::new();
+                    spec.set_dimmed(true).set_italic(true);
+
+                    spec
+                })
+            }
+        }
+    }
+
+    /// Returns the style of the role.
+    pub(crate) fn style(self, styles: &Styles) -> Style {
+        Style {
+            role: Some(self),
+            ..self.spec(styles).into()
+        }
+    }
+
+    /// Returns the role of the given spec, if it is the very spec of a role.
+    ///
+    /// Specs are compared by identity rather than by value, as distinct elements may
+    /// share the same colors.
+    fn of(styles: &Styles, spec: &ColorSpec) -> Option<Role> {
+        Role::ALL
+            .into_iter()
+            .find(|role| std::ptr::eq(role.spec(styles), spec))
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Style {
+    pub foreground: Option<Color>,
+    pub background: Option<Color>,
+    pub bold: bool,
+    pub italic: bool,
+    pub dimmed: bool,
+    pub underline: bool,
+    /// The element the text renders, if known.
+    pub role: Option<Role>,
+}
+
+/// Returns `Style` from `ColorSpec`
+#[doc(hidden)]
+impl From<&ColorSpec> for Style {
+    fn from(spec: &ColorSpec) -> Self {
+        Self {
+            foreground: spec.fg().cloned(),
+            background: spec.bg().cloned(),
+            bold: spec.bold(),
+            italic: spec.italic(),
+            dimmed: spec.dimmed(),
+            underline: spec.underline(),
+            role: None,
+        }
+    }
+}
+
+/// A writer that records the rendered text as styled lines, instead of writing escape sequences.
+///
+/// Text written with one of the specs of the given styles records its role.
+#[derive(Debug)]
+pub(crate) struct StyledLinesWriter<'s> {
+    styles: &'s Styles,
+    lines: Vec<StyledLine>,
+    current: StyledLine,
+    style: Style,
+}
+
+impl<'s> StyledLinesWriter<'s> {
+    pub(crate) fn new(styles: &'s Styles) -> Self {
+        Self {
+            styles,
+            lines: vec![],
+            current: vec![],
+            style: Style::default(),
+        }
+    }
+
+    pub(crate) fn into_lines(mut self) -> Vec<StyledLine> {
+        if !self.current.is_empty() {
+            self.lines.push(self.current);
+        }
+
+        self.lines
+    }
+
+    fn push(&mut self, text: &str) {
+        push(&mut self.current, text, self.style.clone());
+    }
+}
+
+impl std::io::Write for StyledLinesWriter<'_> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let text = String::from_utf8_lossy(buf);
+        let mut lines = text.split('\n');
+
+        if let Some(first) = lines.next() {
+            self.push(first);
+        }
+
+        for line in lines {
+            self.lines.push(std::mem::take(&mut self.current));
+            self.push(line);
+        }
+
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl WriteColor for StyledLinesWriter<'_> {
+    // styles are recorded through `set_color`, returning `false` here keeps escape
+    // sequences out of the recorded text.
+    fn supports_color(&self) -> bool {
+        false
+    }
+
+    fn set_color(&mut self, spec: &ColorSpec) -> std::io::Result<()> {
+        self.style = match Role::of(self.styles, spec) {
+            Some(role) => role.style(self.styles),
+            None => spec.into(),
+        };
+
+        Ok(())
+    }
+
+    fn reset(&mut self) -> std::io::Result<()> {
+        self.style = Style::default();
+
+        Ok(())
+    }
+}
+
+/// Returns `ColorSpec` from `Style`
+#[doc(hidden)]
+impl From<&Style> for ColorSpec {
+    fn from(style: &Style) -> Self {
+        let mut spec = ColorSpec::new();
+        spec.set_fg(style.foreground)
+            .set_bg(style.background)
+            .set_bold(style.bold)
+            .set_italic(style.italic)
+            .set_dimmed(style.dimmed)
+            .set_underline(style.underline);
+
+        spec
+    }
+}
+
+/// Appends the given text to the given line, merging it with the last segment if it shares
+/// its style.
+pub(crate) fn push(line: &mut StyledLine, text: &str, style: Style) {
+    if text.is_empty() {
+        return;
+    }
+
+    match line.last_mut() {
+        Some((last, last_style)) if *last_style == style => last.push_str(text),
+        _ => line.push((text.to_string(), style)),
+    }
+}
+
+/// Returns the lines of the given line, split at its newlines.
+pub(crate) fn split_lines(line: &StyledLine) -> Vec<StyledLine> {
+    let mut lines = vec![vec![]];
+    for (text, style) in line {
+        let mut parts = text.split('\n');
+        if let Some(first) = parts.next() {
+            push(lines.last_mut().unwrap(), first, style.clone());
+        }
+
+        for part in parts {
+            lines.push(vec![]);
+            push(lines.last_mut().unwrap(), part, style.clone());
+        }
+    }
+
+    lines
+}
+
+/// Returns the text of the given line, without styles.
+pub(crate) fn text(line: &StyledLine) -> String {
+    line.iter().map(|(text, _)| text.as_str()).collect()
+}
+
+/// Returns the characters of the given line between `from` and `to`, keeping their styles.
+pub(crate) fn slice(line: &StyledLine, from: usize, to: usize) -> StyledLine {
+    let mut sliced = vec![];
+    let mut position = 0;
+    for (text, style) in line {
+        let length = text.chars().count();
+        let start = from.clamp(position, position + length) - position;
+        let end = to.clamp(position, position + length) - position;
+        if start < end {
+            sliced.push((
+                text.chars().skip(start).take(end - start).collect(),
+                style.clone(),
+            ));
+        }
+
+        position += length;
+    }
+
+    sliced
+}
+
+/// Returns the given line with each of its characters mapped, keeping their styles.
+pub(crate) fn map_chars<F: Fn(char) -> char>(line: &StyledLine, f: F) -> StyledLine {
+    line.iter()
+        .map(|(text, style)| (text.chars().map(&f).collect(), style.clone()))
+        .collect()
+}
+
+/// Returns the given line as HTML segments, classed by their roles.
+pub(crate) fn html_line(line: &StyledLine) -> HtmlLine {
+    line.iter()
+        .map(|(text, style)| HtmlSegment {
+            text: text.clone(),
+            class: style.role.map(Role::class),
+        })
+        .collect()
+}
+
+/// Writes the given lines, applying their styles.
+///
+/// Text with a role is written with the spec of its role in the given styles, so that the
+/// writer can record it in turn.
+pub(crate) fn write_lines<W: WriteColor + ?Sized>(
+    w: &mut W,
+    lines: &[StyledLine],
+    styles: &Styles,
+) -> std::io::Result<()> {
+    for line in lines {
+        for (text, style) in line {
+            if let Some(role) = style.role {
+                w.set_color(role.spec(styles))?;
+            } else if *style == Style::default() {
+                w.reset()?;
+            } else {
+                w.set_color(&style.into())?;
+            }
+
+            write!(w, "{text}")?;
+        }
+
+        w.reset()?;
+        writeln!(w)?;
+    }
+
+    Ok(())
+}